@@ -0,0 +1,189 @@
+use crate::asset::{Asset, AssetRegistry, FeeSchedule};
+use crate::fees::FeePolicy;
+use crate::ledger::COINBASE_ADDRESS;
+use crate::mempool::MAX_BLOCK_TRANSACTIONS;
+use crate::status::TxStatus;
+use crate::{merkle, Amount, Block, Transaction};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use wallet::encryption::{self, Keypair};
+
+pub const CHAINSPEC_FILE: &str = "chainspec.json";
+// Seed akun genesis devnet. Kuncinya sengaja publik, jangan dipakai di luar dev/test.
+pub const DEV_GENESIS_SEED: &str = "devnet-genesis";
+// Akun faucet devnet (lihat `faucet`). Kuncinya dipegang operator devnet, ga ada di repo;
+// faucet di network lain butuh chainspec yang ngasih alokasi ke address dari FAUCET_SEED sendiri.
+pub const DEV_FAUCET_ADDRESS: &str = "4T3m7mFcjsr68mEzTmVS1x5wpZsYN87CyVF7CJtcCCW1";
+
+pub fn seed_keypair(seed: &str) -> Keypair {
+    encryption::keypair_from_seed(Sha256::digest(seed.as_bytes()).into())
+}
+
+pub fn dev_genesis_keypair() -> Keypair {
+    seed_keypair(DEV_GENESIS_SEED)
+}
+
+pub fn dev_genesis_address() -> String {
+    encryption::get_address(&dev_genesis_keypair().public)
+}
+
+// Faucet cuma boleh jalan di network dev/test, dilihat dari nama chainspec
+pub fn is_test_network(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    ["dev", "test", "local"].iter().any(|prefix| name.starts_with(prefix))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenesisAllocation {
+    pub address: String,
+    pub network: String,
+    pub amount: Amount,
+}
+
+// Semua parameter konsensus. Node dengan spec yang sama pasti dapet genesis hash yang sama.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainSpec {
+    pub name: String,
+    pub genesis_timestamp: u64,
+    pub genesis_allocations: Vec<GenesisAllocation>,
+    // Difficulty awal (ekspektasi jumlah hash per block), lihat `difficulty`
+    pub difficulty: u64,
+    pub target_block_time: u64,
+    pub difficulty_window: u64,
+    // Aset yang boleh dipakai transaksi: desimal, jadwal fee, supply cap, mata uang peg
+    pub assets: AssetRegistry,
+    pub fees: FeePolicy,
+    // Subsidi miner per network (satuan terkecil), dibagi dua tiap `halving_interval` block
+    pub block_reward: HashMap<String, Amount>,
+    pub halving_interval: u64,
+}
+
+// Jumlah token dalam satuan desimal ("0.1" SOL), cuma buat nilai bawaan yang pasti valid
+fn token(decimals: u8, value: &str) -> Amount {
+    Amount::parse_decimal(value, decimals).expect("valid default amount")
+}
+
+fn default_asset(symbol: &str, decimals: u8, base_fee: &str, per_byte_fee: &str, supply_cap: Option<&str>) -> Asset {
+    let pegs: &[&str] = if symbol == "USDT" { &["IDR", "USD"] } else { &["IDR", "USD", "USDT"] };
+    Asset {
+        symbol: symbol.to_string(),
+        decimals,
+        fees: FeeSchedule { base_fee: token(decimals, base_fee), per_byte_fee: token(decimals, per_byte_fee) },
+        supply_cap: supply_cap.map(|cap| token(decimals, cap)),
+        pegs: pegs.iter().map(|p| p.to_string()).collect(),
+    }
+}
+
+impl Default for ChainSpec {
+    fn default() -> Self {
+        let assets = AssetRegistry::new(vec![
+            default_asset("BTC", 8, "0.001", "0.0000001", Some("21000000")),
+            default_asset("ETH", 18, "0.01", "0.000001", None),
+            default_asset("SOL", 9, "0.1", "0.00001", None),
+            default_asset("BNB", 18, "2", "0.001", None),
+            default_asset("ADA", 6, "0.17", "0.000044", Some("45000000000")),
+            default_asset("XRP", 6, "0.00001", "0", Some("100000000000")),
+            default_asset("DOT", 10, "0.01", "0.0000001", None),
+            default_asset("MATIC", 18, "0.01", "0.000001", None),
+            default_asset("USDT", 6, "1", "0.001", None),
+        ])
+        .expect("valid default assets");
+        ChainSpec {
+            name: "devnet".to_string(),
+            genesis_timestamp: 1750896000,
+            genesis_allocations: vec![
+                GenesisAllocation { address: dev_genesis_address(), network: "SOL".to_string(), amount: token(9, "100") },
+                GenesisAllocation { address: DEV_FAUCET_ADDRESS.to_string(), network: "SOL".to_string(), amount: token(9, "1000") },
+            ],
+            difficulty: 256,
+            target_block_time: 10,
+            difficulty_window: 30,
+            fees: FeePolicy { congestion_threshold: MAX_BLOCK_TRANSACTIONS, max_congestion_percent: 1000 },
+            block_reward: ["SOL", "BTC", "ETH", "BNB"]
+                .into_iter()
+                .map(|symbol| {
+                    let asset = assets.get(symbol).expect("default asset");
+                    (symbol.to_string(), token(asset.decimals, "50"))
+                })
+                .collect(),
+            assets,
+            halving_interval: 100_000,
+        }
+    }
+}
+
+impl ChainSpec {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read chainspec {}: {}", path.display(), e))?;
+        let spec: ChainSpec = serde_json::from_str(&contents).map_err(|e| format!("Invalid chainspec {}: {}", path.display(), e))?;
+        if spec.halving_interval == 0 {
+            return Err("Invalid chainspec: halving_interval must be greater than zero".to_string());
+        }
+        spec.assets.validate().map_err(|e| format!("Invalid chainspec {}: {}", path.display(), e))?;
+        Ok(spec)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let contents = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, contents + "\n").map_err(|e| format!("Failed to write chainspec {}: {}", path.display(), e))
+    }
+
+    // Pakai file kalau ada, kalau ga ada fallback ke spec devnet bawaan
+    pub fn load_or_default(path: impl AsRef<Path>) -> Result<Self, String> {
+        if path.as_ref().exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn block_subsidy(&self, height: u64, network: &str) -> Amount {
+        let halvings = height / self.halving_interval;
+        let reward = self.block_reward.get(network).copied().unwrap_or_default();
+        Amount::from_units(reward.units().checked_shr(halvings as u32).unwrap_or(0))
+    }
+
+    pub fn genesis_block(&self) -> Block {
+        let transactions = self
+            .genesis_allocations
+            .iter()
+            .enumerate()
+            .map(|(i, alloc)| {
+                let seed = format!("{}:{}:{}:{}:{}", self.name, i, alloc.address, alloc.network, alloc.amount);
+                Transaction {
+                    from: COINBASE_ADDRESS.to_string(),
+                    to: alloc.address.clone(),
+                    amount: alloc.amount,
+                    peg_value: self.assets.native_peg(alloc.amount, &alloc.network),
+                    network: alloc.network.clone(),
+                    fee: Amount::ZERO,
+                    signature: None,
+                    status: TxStatus::Included { block: 0 },
+                    txid: format!("{:x}", Sha256::digest(seed.as_bytes())),
+                    timestamp: self.genesis_timestamp,
+                    nonce: 0,
+                    lock_until: None,
+                    multisig: None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut block = Block {
+            index: 0,
+            timestamp: self.genesis_timestamp,
+            merkle_root: merkle::merkle_root(&transactions),
+            transactions,
+            previous_hash: String::from("0"),
+            hash: String::new(),
+            nonce: 0,
+            difficulty: self.difficulty,
+        };
+        block.calculate_hash();
+        block
+    }
+}
@@ -0,0 +1,45 @@
+use crate::chainspec::ChainSpec;
+use crate::Block;
+
+// Difficulty = rata-rata jumlah hash yang dibutuhkan buat nemu block.
+// Hash valid kalau 64 bit teratasnya <= u64::MAX / difficulty, jadi difficulty 256
+// setara sama "00" di depan hash, tapi naik-turunnya bisa per satuan, ga harus kelipatan 16.
+pub fn target(difficulty: u64) -> u64 {
+    u64::MAX / difficulty.max(1)
+}
+
+pub fn meets_target(hash: &str, difficulty: u64) -> bool {
+    hash.get(..16)
+        .and_then(|prefix| u64::from_str_radix(prefix, 16).ok())
+        .is_some_and(|value| value <= target(difficulty))
+}
+
+// Work satu block sama dengan difficulty-nya (ekspektasi jumlah hash)
+pub fn work(difficulty: u64) -> u128 {
+    difficulty.max(1) as u128
+}
+
+// LWMA: solvetime block terbaru dapet bobot paling besar. Dipakai tiap block
+// setelah chain punya `difficulty_window` block non-genesis. Timestamp yang kejauhan di depan jam
+// node udah ditolak `validation::validate_header`, jadi solvetime ga bisa dipompa buat nurunin difficulty.
+pub fn next_difficulty(chain: &[Block], spec: &ChainSpec) -> u64 {
+    let n = spec.difficulty_window as usize;
+    if n == 0 || chain.len() < n + 2 {
+        return spec.difficulty;
+    }
+
+    let window = &chain[chain.len() - n - 1..];
+    let t = spec.target_block_time.max(1) as u128;
+    let mut weighted_solvetime: u128 = 0;
+    let mut sum_difficulty: u128 = 0;
+    for i in 1..=n {
+        // Timestamp cuma resolusi detik, jadi solvetime 0 dianggap 1 detik
+        let solvetime = window[i].timestamp.saturating_sub(window[i - 1].timestamp) as u128;
+        weighted_solvetime += i as u128 * solvetime.clamp(1, 6 * t);
+        sum_difficulty += window[i].difficulty as u128;
+    }
+
+    let n = n as u128;
+    let next = sum_difficulty * t * (n + 1) / (2 * weighted_solvetime);
+    next.clamp(1, u64::MAX as u128) as u64
+}
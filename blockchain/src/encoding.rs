@@ -0,0 +1,143 @@
+use crate::{Amount, Block, BlockHeader, LockTime, Transaction};
+use sha2::{Digest, Sha256};
+
+// Encoding biner kanonik buat semua hashing (txid, hash block, mining, validasi).
+// Integer big-endian, string = panjang u32 + byte UTF-8, amount = u128 satuan terkecil.
+// Naikin versi ini kalau layout berubah, byte pertama tiap encoding selalu versi.
+pub const ENCODING_VERSION: u8 = 5;
+
+#[derive(Debug)]
+pub struct Encoder {
+    buf: Vec<u8>,
+}
+
+impl Default for Encoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Encoder {
+    pub fn new() -> Self {
+        Encoder { buf: vec![ENCODING_VERSION] }
+    }
+
+    pub fn u8(&mut self, value: u8) -> &mut Self {
+        self.buf.push(value);
+        self
+    }
+
+    pub fn u64(&mut self, value: u64) -> &mut Self {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    pub fn u128(&mut self, value: u128) -> &mut Self {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    pub fn amount(&mut self, value: Amount) -> &mut Self {
+        self.u128(value.units())
+    }
+
+    pub fn bytes(&mut self, value: &[u8]) -> &mut Self {
+        self.buf.extend_from_slice(&(value.len() as u32).to_be_bytes());
+        self.buf.extend_from_slice(value);
+        self
+    }
+
+    pub fn str(&mut self, value: &str) -> &mut Self {
+        self.bytes(value.as_bytes())
+    }
+
+    pub fn opt_str(&mut self, value: Option<&str>) -> &mut Self {
+        match value {
+            Some(v) => self.u8(1).str(v),
+            None => self.u8(0),
+        }
+    }
+
+    pub fn finish(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.buf)
+    }
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+// Isi transaksi tanpa txid, signature, dan status. Ini yang ditandatangani sender.
+pub fn transaction_payload(tx: &Transaction) -> Vec<u8> {
+    let mut encoder = Encoder::new();
+    encoder
+        .str(&tx.from)
+        .str(&tx.to)
+        .amount(tx.amount)
+        .str(&tx.peg_value.to_string())
+        .str(&tx.network)
+        .amount(tx.fee)
+        .u64(tx.timestamp)
+        .u64(tx.nonce);
+    // 0 = ga dikunci, 1 = height, 2 = waktu
+    match tx.lock_until {
+        None => encoder.u8(0),
+        Some(LockTime::Height(height)) => encoder.u8(1).u64(height),
+        Some(LockTime::Time(time)) => encoder.u8(2).u64(time),
+    };
+    encoder.finish()
+}
+
+// Txid = hash payload, transaksi dengan payload sama pasti txid-nya sama
+pub fn transaction_id(tx: &Transaction) -> String {
+    sha256_hex(&transaction_payload(tx))
+}
+
+// Status sengaja ga ikut: itu catatan lokal node, bukan bagian dari transaksi
+pub fn encode_transaction(tx: &Transaction) -> Vec<u8> {
+    let mut encoder = Encoder::new();
+    encoder.bytes(&transaction_payload(tx)).str(&tx.txid).opt_str(tx.signature.as_deref());
+    match &tx.multisig {
+        Some(witness) => {
+            encoder.u8(1).u8(witness.policy.threshold).u8(witness.policy.signers.len() as u8);
+            for signer in &witness.policy.signers {
+                encoder.str(signer);
+            }
+            encoder.u8(witness.signatures.len() as u8);
+            for sig in &witness.signatures {
+                encoder.str(&sig.signer).str(&sig.signature);
+            }
+        }
+        None => {
+            encoder.u8(0);
+        }
+    }
+    encoder.finish()
+}
+
+// Semua field header kecuali nonce, nonce selalu 8 byte terakhir.
+// Transaksi masuk lewat merkle_root, jadi header ukurannya tetap.
+pub fn header_prefix(header: &BlockHeader) -> Vec<u8> {
+    Encoder::new()
+        .u64(header.index)
+        .u64(header.timestamp)
+        .str(&header.previous_hash)
+        .str(&header.merkle_root)
+        .u64(header.difficulty)
+        .finish()
+}
+
+pub fn hash_with_nonce(prefix: &[u8], nonce: u64) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(prefix);
+    hasher.update(nonce.to_be_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+pub fn header_hash(header: &BlockHeader) -> String {
+    hash_with_nonce(&header_prefix(header), header.nonce)
+}
+
+pub fn block_hash(block: &Block) -> String {
+    header_hash(&block.header())
+}
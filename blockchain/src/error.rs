@@ -0,0 +1,121 @@
+use actix_web::http::StatusCode;
+use actix_web::{error, web, HttpRequest, HttpResponse, ResponseError};
+use crate::TxError;
+use serde::Serialize;
+use std::fmt;
+
+// Semua error API dikirim sebagai JSON {"code": ..., "message": ..., "details": ...}.
+// `code` stabil buat dicocokin client, `message` buat dibaca manusia dan boleh berubah.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    // Request ga valid: JSON rusak, field salah, transaksi ditolak aturan chain
+    Validation,
+    InsufficientBalance,
+    InvalidSignature,
+    NotFound,
+    // Transaksi atau proposal yang sama udah ada
+    Conflict,
+    Unauthorized,
+    Forbidden,
+    RateLimited,
+    Internal,
+}
+
+impl ErrorCode {
+    pub fn status(self) -> StatusCode {
+        match self {
+            ErrorCode::Validation | ErrorCode::InvalidSignature => StatusCode::BAD_REQUEST,
+            ErrorCode::InsufficientBalance => StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::Conflict => StatusCode::CONFLICT,
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::Forbidden => StatusCode::FORBIDDEN,
+            ErrorCode::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ApiError {
+    pub code: ErrorCode,
+    pub message: String,
+    pub details: Option<serde_json::Value>,
+}
+
+impl ApiError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        ApiError { code, message: message.into(), details: None }
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Validation, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+}
+
+impl From<&TxError> for ErrorCode {
+    fn from(e: &TxError) -> Self {
+        match e {
+            TxError::Invalid(_) => ErrorCode::Validation,
+            TxError::InsufficientBalance(_) => ErrorCode::InsufficientBalance,
+            TxError::InvalidSignature(_) => ErrorCode::InvalidSignature,
+            TxError::Conflict(_) => ErrorCode::Conflict,
+            TxError::RateLimited(_) => ErrorCode::RateLimited,
+        }
+    }
+}
+
+impl From<TxError> for ApiError {
+    fn from(e: TxError) -> Self {
+        ApiError::new(ErrorCode::from(&e), e.to_string())
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        self.code.status()
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(self)
+    }
+}
+
+// Error extractor bawaan actix (JSON, query, path yang ga bisa di-parse) juga dibungkus jadi ApiError
+pub fn json_config() -> web::JsonConfig {
+    web::JsonConfig::default().error_handler(|e: error::JsonPayloadError, _: &HttpRequest| ApiError::validation(format!("Invalid JSON body: {}", e)).into())
+}
+
+pub fn query_config() -> web::QueryConfig {
+    web::QueryConfig::default().error_handler(|e: error::QueryPayloadError, _: &HttpRequest| ApiError::validation(format!("Invalid query: {}", e)).into())
+}
+
+pub fn path_config() -> web::PathConfig {
+    web::PathConfig::default().error_handler(|e: error::PathError, _: &HttpRequest| ApiError::validation(format!("Invalid path: {}", e)).into())
+}
+
+// Route yang ga ada juga dijawab pakai JSON, bukan body kosong bawaan actix
+pub async fn route_not_found(req: HttpRequest) -> Result<HttpResponse, ApiError> {
+    Err(ApiError::not_found(format!("No route for {} {}", req.method(), req.path())))
+}
@@ -0,0 +1,157 @@
+use crate::{merkle, multisig, Amount, Block, BlockHeader, Blockchain, Transaction};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use wallet::encryption;
+
+// Bentuk response buat endpoint explorer (/blocks, /block/..., /chain/info, /search)
+
+pub const DEFAULT_PAGE_SIZE: usize = 20;
+pub const MAX_PAGE_SIZE: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockSummary {
+    #[serde(flatten)]
+    pub header: BlockHeader,
+    // Jumlah block dari block ini sampai tip, termasuk dirinya sendiri
+    pub confirmations: u64,
+    pub transaction_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockResponse {
+    #[serde(flatten)]
+    pub summary: BlockSummary,
+    // Hash leaf merkle tiap transaksi, urutannya sama dengan `transactions`
+    pub merkle_leaves: Vec<String>,
+    pub transactions: Vec<Transaction>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockPage {
+    pub blocks: Vec<BlockSummary>,
+    pub height: u64,
+    // Nilai `from` buat halaman berikutnya, None kalau udah sampai tip
+    pub next: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainInfo {
+    pub name: String,
+    pub height: u64,
+    pub tip_hash: String,
+    pub difficulty: u64,
+    pub total_work: u128,
+    pub target_block_time: u64,
+    pub mempool_size: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressSummary {
+    pub address: String,
+    pub balances: BTreeMap<String, Amount>,
+    pub nonce: u64,
+    pub next_nonce: u64,
+    pub transaction_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "result", rename_all = "snake_case")]
+pub enum SearchResult {
+    Block(BlockResponse),
+    Transaction(Transaction),
+    Address(AddressSummary),
+}
+
+impl BlockSummary {
+    pub fn of(block: &Block, height: u64) -> Self {
+        BlockSummary {
+            header: block.header(),
+            confirmations: height.saturating_sub(block.index) + 1,
+            transaction_count: block.transactions.len(),
+        }
+    }
+}
+
+impl BlockResponse {
+    pub fn of(block: &Block, height: u64) -> Self {
+        BlockResponse {
+            summary: BlockSummary::of(block, height),
+            merkle_leaves: block.transactions.iter().map(merkle::transaction_hash).collect(),
+            transactions: block.transactions.clone(),
+        }
+    }
+}
+
+fn height(blockchain: &Blockchain) -> u64 {
+    blockchain.chain.len().saturating_sub(1) as u64
+}
+
+pub fn block(blockchain: &Blockchain, index: u64) -> Option<BlockResponse> {
+    let block = blockchain.chain.get(usize::try_from(index).ok()?)?;
+    Some(BlockResponse::of(block, height(blockchain)))
+}
+
+pub fn block_by_hash(blockchain: &Blockchain, hash: &str) -> Option<BlockResponse> {
+    blockchain.block_by_hash(hash).map(|block| BlockResponse::of(block, height(blockchain)))
+}
+
+pub fn latest_block(blockchain: &Blockchain) -> Option<BlockResponse> {
+    blockchain.chain.last().map(|block| BlockResponse::of(block, height(blockchain)))
+}
+
+// Block urut naik mulai dari index `from`, paling banyak `MAX_PAGE_SIZE`
+pub fn blocks(blockchain: &Blockchain, from: u64, limit: usize) -> BlockPage {
+    let height = height(blockchain);
+    let limit = limit.clamp(1, MAX_PAGE_SIZE);
+    let blocks: Vec<BlockSummary> = blockchain
+        .chain
+        .iter()
+        .skip(usize::try_from(from).unwrap_or(usize::MAX))
+        .take(limit)
+        .map(|block| BlockSummary::of(block, height))
+        .collect();
+    let next = blocks.last().map(|b| b.header.index + 1).filter(|next| *next <= height);
+    BlockPage { blocks, height, next }
+}
+
+pub fn chain_info(blockchain: &Blockchain) -> ChainInfo {
+    ChainInfo {
+        name: blockchain.spec.name.clone(),
+        height: height(blockchain),
+        tip_hash: blockchain.chain.last().map(|b| b.hash.clone()).unwrap_or_default(),
+        difficulty: blockchain.difficulty,
+        total_work: blockchain.total_work(),
+        target_block_time: blockchain.target_block_time,
+        mempool_size: blockchain.mempool.len(),
+    }
+}
+
+pub fn address_summary(blockchain: &Blockchain, address: &str) -> AddressSummary {
+    AddressSummary {
+        address: address.to_string(),
+        balances: blockchain.wallet.balances(address),
+        nonce: blockchain.wallet.get_nonce(address),
+        next_nonce: blockchain.mempool.next_nonce(&blockchain.wallet, address),
+        transaction_count: blockchain.index.address_transactions(address).len(),
+    }
+}
+
+// Urutan: index block, hash block, txid, lalu address. Address dianggap ketemu kalau formatnya
+// valid atau pernah muncul di chain.
+pub fn search(blockchain: &Blockchain, query: &str) -> Option<SearchResult> {
+    let query = query.trim();
+    if let Ok(index) = query.parse::<u64>() {
+        if let Some(block) = block(blockchain, index) {
+            return Some(SearchResult::Block(block));
+        }
+    }
+    if let Some(block) = block_by_hash(blockchain, query) {
+        return Some(SearchResult::Block(block));
+    }
+    if let Some(tx) = blockchain.find_transaction(query) {
+        return Some(SearchResult::Transaction(tx));
+    }
+    let summary = address_summary(blockchain, query);
+    let valid = encryption::public_key_from_address(query).is_ok() || multisig::is_multisig_address(query);
+    (valid || summary.transaction_count > 0 || !summary.balances.is_empty()).then_some(SearchResult::Address(summary))
+}
@@ -0,0 +1,235 @@
+use crate::chainspec::{self, ChainSpec};
+use crate::{multisig, Amount, Transaction};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::net::IpAddr;
+use wallet::encryption::{self, Keypair};
+
+// Faucet buat bagi-bagi token di devnet/testnet. Dananya transfer biasa (ditandatangani, bayar fee)
+// dari akun yang dapet alokasi genesis, jadi ga ada token yang dicetak di luar chainspec.
+// Mati secara default dan ditolak kalau chainspec-nya bukan network dev/test.
+
+pub const FAUCET_ENABLED_ENV: &str = "FAUCET_ENABLED";
+// Seed akun faucet (di-hash SHA-256 jadi secret key), wajib diisi kalau faucet diaktifkan
+pub const FAUCET_SEED_ENV: &str = "FAUCET_SEED";
+pub const FAUCET_NETWORK_ENV: &str = "FAUCET_NETWORK";
+// Jumlah per permintaan dalam satuan desimal aset, misal "1.5"
+pub const FAUCET_DRIP_ENV: &str = "FAUCET_DRIP";
+pub const FAUCET_ADDRESS_COOLDOWN_ENV: &str = "FAUCET_ADDRESS_COOLDOWN_SECS";
+pub const FAUCET_IP_COOLDOWN_ENV: &str = "FAUCET_IP_COOLDOWN_SECS";
+pub const DEFAULT_DRIP: &str = "1";
+pub const DEFAULT_ADDRESS_COOLDOWN_SECS: u64 = 24 * 3600;
+pub const DEFAULT_IP_COOLDOWN_SECS: u64 = 3600;
+
+#[derive(Clone)]
+pub struct FaucetConfig {
+    pub enabled: bool,
+    pub seed: String,
+    pub network: String,
+    pub drip: String,
+    pub address_cooldown_secs: u64,
+    pub ip_cooldown_secs: u64,
+}
+
+impl fmt::Debug for FaucetConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FaucetConfig")
+            .field("enabled", &self.enabled)
+            .field("network", &self.network)
+            .field("drip", &self.drip)
+            .field("address_cooldown_secs", &self.address_cooldown_secs)
+            .field("ip_cooldown_secs", &self.ip_cooldown_secs)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for FaucetConfig {
+    fn default() -> Self {
+        FaucetConfig {
+            enabled: false,
+            seed: String::new(),
+            network: "SOL".to_string(),
+            drip: DEFAULT_DRIP.to_string(),
+            address_cooldown_secs: DEFAULT_ADDRESS_COOLDOWN_SECS,
+            ip_cooldown_secs: DEFAULT_IP_COOLDOWN_SECS,
+        }
+    }
+}
+
+fn env_secs(name: &str, default: u64) -> u64 {
+    match std::env::var(name).map(|v| v.parse::<u64>()) {
+        Ok(Ok(secs)) => secs,
+        Ok(Err(_)) => {
+            eprintln!("Invalid {}, using {} seconds", name, default);
+            default
+        }
+        Err(_) => default,
+    }
+}
+
+impl FaucetConfig {
+    pub fn from_env() -> Self {
+        let mut config = FaucetConfig {
+            enabled: matches!(std::env::var(FAUCET_ENABLED_ENV).as_deref(), Ok("1") | Ok("true")),
+            ..FaucetConfig::default()
+        };
+        if let Ok(seed) = std::env::var(FAUCET_SEED_ENV) {
+            config.seed = seed;
+        }
+        if let Ok(network) = std::env::var(FAUCET_NETWORK_ENV) {
+            config.network = network.to_uppercase();
+        }
+        if let Ok(drip) = std::env::var(FAUCET_DRIP_ENV) {
+            config.drip = drip;
+        }
+        config.address_cooldown_secs = env_secs(FAUCET_ADDRESS_COOLDOWN_ENV, DEFAULT_ADDRESS_COOLDOWN_SECS);
+        config.ip_cooldown_secs = env_secs(FAUCET_IP_COOLDOWN_ENV, DEFAULT_IP_COOLDOWN_SECS);
+        config
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FaucetInfo {
+    pub address: String,
+    pub network: String,
+    pub drip: Amount,
+    pub display_drip: String,
+    pub address_cooldown_secs: u64,
+    pub ip_cooldown_secs: u64,
+}
+
+#[derive(Clone)]
+pub struct Faucet {
+    // Keypair ed25519 ga bisa di-clone, jadi yang disimpan seed-nya
+    seed: String,
+    address: String,
+    network: String,
+    drip: Amount,
+    address_cooldown_secs: u64,
+    ip_cooldown_secs: u64,
+    // Kapan terakhir dapet dana, yang udah lewat cooldown dibuang tiap ada permintaan
+    last_by_address: HashMap<String, u64>,
+    last_by_ip: HashMap<IpAddr, u64>,
+}
+
+impl fmt::Debug for Faucet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Faucet").field("address", &self.address).field("network", &self.network).field("drip", &self.drip).finish_non_exhaustive()
+    }
+}
+
+impl Faucet {
+    pub fn new(config: FaucetConfig, spec: &ChainSpec) -> Result<Self, String> {
+        if !config.enabled {
+            return Err("Faucet is disabled".to_string());
+        }
+        if !chainspec::is_test_network(&spec.name) {
+            return Err(format!("Faucet is only available on dev and test networks, not {}", spec.name));
+        }
+        if config.seed.is_empty() {
+            return Err(format!("{} is required to enable the faucet", FAUCET_SEED_ENV));
+        }
+        let asset = spec.assets.require(&config.network)?;
+        let drip = Amount::parse_decimal(&config.drip, asset.decimals)?;
+        if drip.is_zero() {
+            return Err("Faucet drip must be greater than zero".to_string());
+        }
+        let address = encryption::get_address(&chainspec::seed_keypair(&config.seed).public);
+        if !spec.genesis_allocations.iter().any(|a| a.address == address && a.network == asset.symbol) {
+            return Err(format!("Faucet address {} has no {} genesis allocation", address, asset.symbol));
+        }
+        Ok(Faucet {
+            seed: config.seed,
+            address,
+            network: asset.symbol.clone(),
+            drip,
+            address_cooldown_secs: config.address_cooldown_secs,
+            ip_cooldown_secs: config.ip_cooldown_secs,
+            last_by_address: HashMap::new(),
+            last_by_ip: HashMap::new(),
+        })
+    }
+
+    // None kalau faucet ga diaktifkan, error konfigurasi cuma di-log
+    pub fn from_env(spec: &ChainSpec) -> Option<Self> {
+        let config = FaucetConfig::from_env();
+        if !config.enabled {
+            return None;
+        }
+        match Faucet::new(config, spec) {
+            Ok(faucet) => Some(faucet),
+            Err(e) => {
+                eprintln!("Faucet not started: {}", e);
+                None
+            }
+        }
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn network(&self) -> &str {
+        &self.network
+    }
+
+    pub fn keypair(&self) -> Keypair {
+        chainspec::seed_keypair(&self.seed)
+    }
+
+    pub fn info(&self, spec: &ChainSpec) -> FaucetInfo {
+        FaucetInfo {
+            address: self.address.clone(),
+            network: self.network.clone(),
+            drip: self.drip,
+            display_drip: spec.assets.display_amount(self.drip, &self.network),
+            address_cooldown_secs: self.address_cooldown_secs,
+            ip_cooldown_secs: self.ip_cooldown_secs,
+        }
+    }
+
+    pub fn check_recipient(&self, to: &str) -> Result<(), String> {
+        if encryption::public_key_from_address(to).is_err() && !multisig::is_multisig_address(to) {
+            return Err(format!("Invalid address {}", to));
+        }
+        if to == self.address {
+            return Err("Faucet cannot fund itself".to_string());
+        }
+        Ok(())
+    }
+
+    // Error kalau address atau IP-nya masih kena cooldown
+    pub fn check(&self, to: &str, ip: Option<IpAddr>, now: u64) -> Result<(), String> {
+        if let Some(wait) = remaining(self.last_by_address.get(to), self.address_cooldown_secs, now) {
+            return Err(format!("Address {} already received faucet funds, try again in {}s", to, wait));
+        }
+        if let Some(wait) = ip.and_then(|ip| remaining(self.last_by_ip.get(&ip), self.ip_cooldown_secs, now)) {
+            return Err(format!("Too many faucet requests from this IP, try again in {}s", wait));
+        }
+        Ok(())
+    }
+
+    // Transfer bertanda tangan dari akun faucet; nonce dan fee diisi pemanggil dari mempool
+    pub fn transfer(&self, to: &str, spec: &ChainSpec, nonce: u64, fee: Amount) -> Transaction {
+        let peg = spec.assets.native_peg(self.drip, &self.network);
+        let mut tx = Transaction::new(self.address.clone(), to.to_string(), self.drip, peg, self.network.clone(), fee).with_nonce(nonce);
+        tx.sign(&self.keypair());
+        tx
+    }
+
+    pub fn record(&mut self, to: &str, ip: Option<IpAddr>, now: u64) {
+        let (address_cooldown, ip_cooldown) = (self.address_cooldown_secs, self.ip_cooldown_secs);
+        self.last_by_address.retain(|_, at| now.saturating_sub(*at) < address_cooldown);
+        self.last_by_ip.retain(|_, at| now.saturating_sub(*at) < ip_cooldown);
+        self.last_by_address.insert(to.to_string(), now);
+        if let Some(ip) = ip {
+            self.last_by_ip.insert(ip, now);
+        }
+    }
+}
+
+fn remaining(last: Option<&u64>, cooldown: u64, now: u64) -> Option<u64> {
+    let elapsed = now.saturating_sub(*last?);
+    (elapsed < cooldown).then(|| cooldown - elapsed)
+}
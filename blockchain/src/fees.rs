@@ -0,0 +1,116 @@
+use crate::asset::AssetRegistry;
+use crate::{encoding, ledger, Amount, Transaction};
+use serde::{Deserialize, Serialize};
+
+// Signature ed25519 ga ikut di payload tapi tetap ikut dihitung ke ukuran transaksi
+pub const SIGNATURE_BYTES: usize = 64;
+// Public key ed25519 tiap signer di policy multisig
+pub const PUBLIC_KEY_BYTES: usize = 32;
+// Ukuran kira-kira transfer biasa (address base58 44 karakter), dipakai kalau estimasi ga dikasih ukuran
+pub const TYPICAL_TRANSFER_BYTES: usize = 230;
+
+// Jadwal fee (base + per byte) dipegang tiap aset di registry. Ledger (dan validasi chain)
+// cuma ngecek fee minimum `base_fee + per_byte_fee * ukuran`, karena itu harus sama di semua node.
+// Pengali congestion cuma dipakai mempool buat nyaring transaksi waktu lagi rame, plus buat estimasi di API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeePolicy {
+    // Jumlah transaksi pending yang masih dianggap normal, di atas ini fee minimum mempool naik
+    pub congestion_threshold: usize,
+    // Batas atas pengali congestion dalam persen (100 = 1x)
+    pub max_congestion_percent: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeEstimate {
+    pub network: String,
+    pub base_fee: Amount,
+    pub per_byte_fee: Amount,
+    pub size: usize,
+    pub minimum_fee: Amount,
+    pub pending: usize,
+    pub congestion_percent: u64,
+    pub recommended_fee: Amount,
+    pub display_recommended_fee: String,
+}
+
+// Ukuran yang kena fee: payload yang ditandatangani plus signature-nya. Multisig dihitung
+// policy-nya plus `threshold` signature, jadi fee udah bisa dihitung sebelum ada yang tanda tangan.
+pub fn transaction_size(tx: &Transaction) -> usize {
+    let witness = match &tx.multisig {
+        Some(witness) => 2 + witness.policy.signers.len() * PUBLIC_KEY_BYTES + witness.policy.threshold as usize * SIGNATURE_BYTES,
+        None => SIGNATURE_BYTES,
+    };
+    encoding::transaction_payload(tx).len() + witness
+}
+
+pub fn minimum_fee(assets: &AssetRegistry, tx: &Transaction) -> Result<Amount, String> {
+    assets.require(&tx.network)?.fees.minimum_fee(transaction_size(tx))
+}
+
+// Aturan konsensus, dipanggil ledger buat tiap transaksi. Coinbase ga bayar fee.
+pub fn check(assets: &AssetRegistry, tx: &Transaction) -> Result<(), String> {
+    if ledger::is_coinbase(tx) {
+        return Ok(());
+    }
+    let minimum = minimum_fee(assets, tx)?;
+    if tx.fee < minimum {
+        return Err(format!(
+            "Fee {} {} for transaction {} is below the minimum {}",
+            tx.fee, tx.network, tx.txid, minimum
+        ));
+    }
+    Ok(())
+}
+
+impl FeePolicy {
+    // 100% sampai `congestion_threshold`, lalu naik 100% tiap kelipatan threshold berikutnya
+    pub fn congestion_percent(&self, pending: usize) -> u64 {
+        if self.congestion_threshold == 0 || pending <= self.congestion_threshold {
+            return 100;
+        }
+        let excess = (pending - self.congestion_threshold) as u64;
+        let percent = 100 + excess * 100 / self.congestion_threshold as u64;
+        percent.min(self.max_congestion_percent.max(100))
+    }
+
+    fn with_congestion(&self, fee: Amount, pending: usize) -> Result<Amount, String> {
+        Ok(fee.checked_mul(self.congestion_percent(pending) as u128)?.div_floor(100))
+    }
+
+    // Syarat masuk mempool: fee minimum dikali pengali congestion saat ini
+    pub fn check_admission(&self, assets: &AssetRegistry, tx: &Transaction, pending: usize) -> Result<(), String> {
+        check(assets, tx)?;
+        if ledger::is_coinbase(tx) {
+            return Ok(());
+        }
+        let required = self.with_congestion(minimum_fee(assets, tx)?, pending)?;
+        if tx.fee < required {
+            return Err(format!(
+                "Fee {} {} is below the current minimum {} ({}% congestion, {} pending)",
+                tx.fee,
+                tx.network,
+                required,
+                self.congestion_percent(pending),
+                pending
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn estimate(&self, assets: &AssetRegistry, network: &str, size: usize, pending: usize) -> Result<FeeEstimate, String> {
+        let asset = assets.require(network)?;
+        let minimum_fee = asset.fees.minimum_fee(size)?;
+        let recommended_fee = self.with_congestion(minimum_fee, pending)?;
+        Ok(FeeEstimate {
+            network: network.to_string(),
+            base_fee: asset.fees.base_fee,
+            per_byte_fee: asset.fees.per_byte_fee,
+            size,
+            minimum_fee,
+            pending,
+            congestion_percent: self.congestion_percent(pending),
+            recommended_fee,
+            display_recommended_fee: asset.display_amount(recommended_fee),
+        })
+    }
+}
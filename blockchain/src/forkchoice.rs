@@ -0,0 +1,98 @@
+use crate::difficulty;
+use crate::{Block, Transaction};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+// Semua block yang pernah diterima (termasuk cabang samping), di-key pakai hash.
+// Tiap block nyimpen cumulative work dari genesis sampai block itu.
+#[derive(Debug, Clone, Default)]
+pub struct BlockTree {
+    blocks: HashMap<String, Block>,
+    work: HashMap<String, u128>,
+    tips: HashSet<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChainTip {
+    pub hash: String,
+    pub height: u64,
+    pub work: u128,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReorgEvent {
+    pub depth: u64,
+    pub fork_height: u64,
+    pub old_tip: String,
+    pub new_tip: String,
+    // Transaksi dari cabang lama yang ga ada di cabang baru
+    pub dropped: Vec<Transaction>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum BlockOutcome {
+    Extended { height: u64 },
+    SideBranch { height: u64, work: u128 },
+    Reorganized(ReorgEvent),
+    AlreadyKnown,
+}
+
+impl BlockTree {
+    pub fn new(genesis: Block) -> Self {
+        let mut tree = BlockTree::default();
+        tree.work.insert(genesis.hash.clone(), difficulty::work(genesis.difficulty));
+        tree.tips.insert(genesis.hash.clone());
+        tree.blocks.insert(genesis.hash.clone(), genesis);
+        tree
+    }
+
+    pub fn contains(&self, hash: &str) -> bool {
+        self.blocks.contains_key(hash)
+    }
+
+    pub fn get(&self, hash: &str) -> Option<&Block> {
+        self.blocks.get(hash)
+    }
+
+    pub fn work(&self, hash: &str) -> u128 {
+        self.work.get(hash).copied().unwrap_or(0)
+    }
+
+    // Parent harus udah ada di tree
+    pub fn insert(&mut self, block: Block) -> Result<u128, String> {
+        let parent_work = self
+            .work
+            .get(&block.previous_hash)
+            .copied()
+            .ok_or_else(|| format!("Unknown parent {} for block #{}", block.previous_hash, block.index))?;
+        let work = parent_work + difficulty::work(block.difficulty);
+        self.tips.remove(&block.previous_hash);
+        self.tips.insert(block.hash.clone());
+        self.work.insert(block.hash.clone(), work);
+        self.blocks.insert(block.hash.clone(), block);
+        Ok(work)
+    }
+
+    // Urutan genesis..=hash
+    pub fn branch(&self, hash: &str) -> Vec<Block> {
+        let mut branch = Vec::new();
+        let mut current = self.blocks.get(hash);
+        while let Some(block) = current {
+            branch.push(block.clone());
+            current = self.blocks.get(&block.previous_hash);
+        }
+        branch.reverse();
+        branch
+    }
+
+    pub fn tips(&self) -> Vec<ChainTip> {
+        self.tips
+            .iter()
+            .filter_map(|hash| {
+                let block = self.blocks.get(hash)?;
+                Some(ChainTip { hash: hash.clone(), height: block.index, work: self.work(hash) })
+            })
+            .collect()
+    }
+}
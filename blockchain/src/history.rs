@@ -0,0 +1,157 @@
+use crate::status::TxStatus;
+use crate::{Blockchain, Transaction};
+use serde::{Deserialize, Serialize};
+
+// History satu address buat GET /history/{address}: transaksi di chain aktif (dari `index`) lalu
+// yang masih pending di mempool, diurut berdasarkan waktu block. Halaman berikutnya diambil pakai
+// `next_cursor`, jadi address yang rame ga perlu dikirim sekaligus.
+
+pub const DEFAULT_HISTORY_LIMIT: usize = 50;
+pub const MAX_HISTORY_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Sent,
+    Received,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusFilter {
+    Pending,
+    Included,
+    Confirmed,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Order {
+    Asc,
+    // Paling baru duluan
+    #[default]
+    Desc,
+}
+
+// Semua filter opsional. Range waktu dan height inklusif; transaksi pending ga punya height,
+// jadi ga ikut kalau `from_height`/`to_height` diisi.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HistoryQuery {
+    pub direction: Option<Direction>,
+    pub network: Option<String>,
+    pub status: Option<StatusFilter>,
+    pub from_time: Option<u64>,
+    pub to_time: Option<u64>,
+    pub from_height: Option<u64>,
+    pub to_height: Option<u64>,
+    #[serde(default)]
+    pub order: Order,
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryPage {
+    pub address: String,
+    pub transactions: Vec<Transaction>,
+    // Kirim balik sebagai `cursor` buat halaman berikutnya, None kalau udah habis
+    pub next_cursor: Option<String>,
+}
+
+// Satu baris history: transaksi plus waktu dan height block-nya (None kalau masih pending)
+struct Entry {
+    tx: Transaction,
+    time: u64,
+    height: Option<u64>,
+}
+
+impl HistoryQuery {
+    fn matches(&self, address: &str, entry: &Entry) -> bool {
+        let tx = &entry.tx;
+        let direction = match self.direction {
+            Some(Direction::Sent) => tx.from == address,
+            Some(Direction::Received) => tx.to == address,
+            None => true,
+        };
+        let status = match self.status {
+            Some(StatusFilter::Pending) => tx.status == TxStatus::Pending,
+            Some(StatusFilter::Included) => matches!(tx.status, TxStatus::Included { .. }),
+            Some(StatusFilter::Confirmed) => matches!(tx.status, TxStatus::Confirmed { .. }),
+            None => true,
+        };
+        let height = match entry.height {
+            Some(h) => self.from_height.is_none_or(|from| h >= from) && self.to_height.is_none_or(|to| h <= to),
+            None => self.from_height.is_none() && self.to_height.is_none(),
+        };
+        direction
+            && status
+            && height
+            && self.network.as_ref().is_none_or(|n| n.eq_ignore_ascii_case(&tx.network))
+            && self.from_time.is_none_or(|from| entry.time >= from)
+            && self.to_time.is_none_or(|to| entry.time <= to)
+    }
+}
+
+pub fn address_history(blockchain: &Blockchain, address: &str, query: &HistoryQuery) -> Result<HistoryPage, String> {
+    let limit = query.limit.unwrap_or(DEFAULT_HISTORY_LIMIT).clamp(1, MAX_HISTORY_LIMIT);
+    let txids = blockchain.index.address_transactions(address);
+    let tip = blockchain.chain.len().saturating_sub(1) as u64;
+    let mut pending: Vec<Transaction> = blockchain.mempool.transactions().into_iter().filter(|tx| tx.from == address || tx.to == address).collect();
+    pending.sort_by(|a, b| (a.timestamp, &a.txid).cmp(&(b.timestamp, &b.txid)));
+
+    // Posisi 0..txids.len() = chain, sisanya mempool
+    let total = txids.len() + pending.len();
+    let entry = |position: usize| -> Option<Entry> {
+        match txids.get(position) {
+            Some(txid) => {
+                let (block, i) = blockchain.chain_transaction(txid)?;
+                let mut tx = block.transactions[i].clone();
+                tx.status = TxStatus::in_chain(block.index, tip);
+                Some(Entry { tx, time: block.timestamp, height: Some(block.index) })
+            }
+            None => {
+                let tx = pending.get(position - txids.len())?.clone();
+                Some(Entry { time: tx.timestamp, tx, height: None })
+            }
+        }
+    };
+    // Waktu dan height block ga pernah turun sepanjang chain, jadi batas range di chain bisa dicari biner
+    let key = |txid: &String| blockchain.chain_transaction(txid).map(|(b, _)| (b.index, b.timestamp)).unwrap_or_default();
+    let lower = txids.partition_point(|txid| {
+        let (height, time) = key(txid);
+        query.from_height.is_some_and(|from| height < from) || query.from_time.is_some_and(|from| time < from)
+    });
+    let upper = txids.partition_point(|txid| {
+        let (height, time) = key(txid);
+        query.to_height.is_none_or(|to| height <= to) && query.to_time.is_none_or(|to| time <= to)
+    });
+
+    // Cursor = posisi berikutnya yang belum dilihat
+    let cursor = match &query.cursor {
+        Some(cursor) => Some(cursor.parse::<usize>().map_err(|_| format!("Invalid history cursor {:?}", cursor))?),
+        None => None,
+    };
+    let positions: Box<dyn Iterator<Item = usize>> = match query.order {
+        Order::Asc => {
+            let from = cursor.unwrap_or(0);
+            Box::new((from.max(lower)..upper.max(lower)).chain(from.max(txids.len())..total))
+        }
+        Order::Desc => {
+            let end = cursor.map_or(total, |c| c.saturating_add(1));
+            Box::new((txids.len()..end.clamp(txids.len(), total)).rev().chain((lower..end.clamp(lower, upper.max(lower))).rev()))
+        }
+    };
+
+    let mut transactions = Vec::new();
+    let mut next_cursor = None;
+    for position in positions {
+        if transactions.len() == limit {
+            next_cursor = Some(position.to_string());
+            break;
+        }
+        if let Some(entry) = entry(position).filter(|e| query.matches(address, e)) {
+            transactions.push(entry.tx);
+        }
+    }
+    Ok(HistoryPage { address: address.to_string(), transactions, next_cursor })
+}
@@ -0,0 +1,104 @@
+use crate::Block;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+// Index chain aktif: txid -> posisi, address -> txid, hash block -> height.
+// Di-update per block waktu block nyambung ke tip atau di-rollback waktu reorg, jadi nambah block
+// ga perlu jalan ulang dari genesis. Snapshot-nya disimpan di sebelah block store (lihat `storage`).
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxLocation {
+    pub block: u64,
+    pub position: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChainIndex {
+    // Hash block terakhir yang udah masuk index, buat ngecek snapshot masih cocok sama chain
+    tip: String,
+    transactions: HashMap<String, TxLocation>,
+    // Urut sesuai urutan di chain, paling lama duluan
+    addresses: HashMap<String, Vec<String>>,
+    blocks: HashMap<String, u64>,
+}
+
+impl ChainIndex {
+    pub fn build(chain: &[Block]) -> Self {
+        let mut index = ChainIndex::default();
+        for block in chain {
+            index.add_block(block);
+        }
+        index
+    }
+
+    pub fn tip(&self) -> &str {
+        &self.tip
+    }
+
+    pub fn transaction(&self, txid: &str) -> Option<TxLocation> {
+        self.transactions.get(txid).copied()
+    }
+
+    pub fn address_transactions(&self, address: &str) -> &[String] {
+        self.addresses.get(address).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn block_height(&self, hash: &str) -> Option<u64> {
+        self.blocks.get(hash).copied()
+    }
+
+    pub fn transaction_count(&self) -> usize {
+        self.transactions.len()
+    }
+
+    // Block harus nyambung ke `tip`. Txid yang udah ada di block sebelumnya ga ditimpa.
+    pub fn add_block(&mut self, block: &Block) {
+        self.blocks.insert(block.hash.clone(), block.index);
+        for (position, tx) in block.transactions.iter().enumerate() {
+            if self.transactions.contains_key(&tx.txid) {
+                continue;
+            }
+            self.transactions.insert(tx.txid.clone(), TxLocation { block: block.index, position });
+            for address in participants(&tx.from, &tx.to) {
+                self.addresses.entry(address.to_string()).or_default().push(tx.txid.clone());
+            }
+        }
+        self.tip = block.hash.clone();
+    }
+
+    // Kebalikan `add_block`, cuma boleh dipanggil buat block di tip (urut mundur waktu reorg)
+    pub fn remove_block(&mut self, block: &Block) {
+        let removed: HashSet<&str> = block
+            .transactions
+            .iter()
+            .filter(|tx| self.transactions.get(&tx.txid).is_some_and(|loc| loc.block == block.index))
+            .map(|tx| tx.txid.as_str())
+            .collect();
+        for tx in block.transactions.iter().filter(|tx| removed.contains(tx.txid.as_str())) {
+            for address in participants(&tx.from, &tx.to) {
+                if let Some(txids) = self.addresses.get_mut(address) {
+                    // Txid block ini pasti ada di ekor list
+                    while txids.last().is_some_and(|id| removed.contains(id.as_str())) {
+                        txids.pop();
+                    }
+                    if txids.is_empty() {
+                        self.addresses.remove(address);
+                    }
+                }
+            }
+        }
+        for txid in removed {
+            self.transactions.remove(txid);
+        }
+        self.blocks.remove(&block.hash);
+        self.tip = block.previous_hash.clone();
+    }
+}
+
+fn participants<'a>(from: &'a str, to: &'a str) -> Vec<&'a str> {
+    if from == to {
+        vec![from]
+    } else {
+        vec![from, to]
+    }
+}
@@ -0,0 +1,184 @@
+use crate::chainspec::ChainSpec;
+use crate::{encoding, fees, validation, Amount, Block, Transaction, TxError, Wallet};
+use std::collections::{BTreeMap, HashSet};
+
+// Transaksi dari address ini nge-mint saldo baru (reward miner), ga ada yang di-debit
+pub const COINBASE_ADDRESS: &str = "network";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceChange {
+    pub address: String,
+    pub network: String,
+    pub amount: Amount,
+    pub credit: bool,
+}
+
+impl BalanceChange {
+    fn apply(&self, wallet: &mut Wallet) -> Result<(), String> {
+        if self.credit {
+            wallet.credit(&self.address, &self.network, self.amount)
+        } else {
+            wallet.debit(&self.address, &self.network, self.amount)
+        }
+    }
+
+    fn inverse(&self) -> BalanceChange {
+        BalanceChange { credit: !self.credit, ..self.clone() }
+    }
+}
+
+pub fn is_coinbase(tx: &Transaction) -> bool {
+    tx.from == COINBASE_ADDRESS
+}
+
+// Semua perubahan saldo dari satu transaksi, murni dari isi transaksinya
+pub fn transaction_changes(tx: &Transaction) -> Result<Vec<BalanceChange>, String> {
+    let mut changes = Vec::with_capacity(2);
+    if !is_coinbase(tx) {
+        changes.push(BalanceChange {
+            address: tx.from.clone(),
+            network: tx.network.clone(),
+            amount: tx.total_debit()?,
+            credit: false,
+        });
+    }
+    changes.push(BalanceChange {
+        address: tx.to.clone(),
+        network: tx.network.clone(),
+        amount: tx.amount,
+        credit: true,
+    });
+    Ok(changes)
+}
+
+pub fn block_changes(block: &Block) -> Result<Vec<BalanceChange>, String> {
+    let mut changes = Vec::new();
+    for tx in &block.transactions {
+        changes.extend(transaction_changes(tx)?);
+    }
+    Ok(changes)
+}
+
+// Aturan yang cuma bergantung ke isi transaksi dan registry aset (tanpa saldo)
+pub fn check_transaction(tx: &Transaction, spec: &ChainSpec) -> Result<(), TxError> {
+    tx.validate()?;
+    spec.assets.check_peg(&tx.network, &tx.peg_value).map_err(TxError::Invalid)?;
+    fees::check(&spec.assets, tx).map_err(TxError::Invalid)
+}
+
+pub fn apply_transaction(wallet: &mut Wallet, tx: &Transaction, spec: &ChainSpec) -> Result<(), TxError> {
+    check_transaction(tx, spec)?;
+    if is_coinbase(tx) {
+        if let Some(cap) = spec.assets.require(&tx.network).map_err(TxError::Invalid)?.supply_cap {
+            let supply = wallet.total_supply(&tx.network).checked_add(tx.amount).map_err(TxError::Invalid)?;
+            if supply > cap {
+                return Err(TxError::Invalid(format!("Minting {} {} would exceed the supply cap {}", tx.amount, tx.network, cap)));
+            }
+        }
+    } else {
+        let expected = wallet.get_nonce(&tx.from);
+        if tx.nonce != expected {
+            return Err(TxError::Invalid(format!("Invalid nonce for {}: expected {}, got {}", tx.from, expected, tx.nonce)));
+        }
+    }
+    // Debit duluan, jadi saldo kurang langsung gagal sebelum ada yang dikredit.
+    // Kalau kredit overflow, perubahan sebelumnya dibalikin lagi.
+    let changes = transaction_changes(tx).map_err(TxError::Invalid)?;
+    for (i, change) in changes.iter().enumerate() {
+        if let Err(e) = change.apply(wallet) {
+            for applied in changes[..i].iter().rev() {
+                applied.inverse().apply(wallet).map_err(TxError::Invalid)?;
+            }
+            // Debit cuma bisa gagal karena saldo kurang
+            return Err(if change.credit { TxError::Invalid(e) } else { TxError::InsufficientBalance(e) });
+        }
+    }
+    if !is_coinbase(tx) {
+        wallet.set_nonce(&tx.from, tx.nonce + 1);
+    }
+    Ok(())
+}
+
+// Reward maksimal miner buat satu network di block `height`: setengah fee network itu plus subsidi
+pub fn block_reward(spec: &ChainSpec, height: u64, network: &str, fees: Amount) -> Amount {
+    fees.div_floor(2).saturating_add(spec.block_subsidy(height, network))
+}
+
+// Aturan coinbase di block selain genesis (alokasi genesis udah terikat ke hash chainspec):
+// paling banyak satu per network, ditaruh sesudah semua transaksi user, nonce-nya = height block,
+// txid-nya hash payload, dan ga boleh lebih dari `block_reward`. Supply cap dicek
+// `apply_transaction` waktu mint-nya di-apply.
+pub fn check_coinbase(block: &Block, spec: &ChainSpec) -> Result<(), String> {
+    if block.index == 0 {
+        return Ok(());
+    }
+    let mut network_fees: BTreeMap<&str, Amount> = BTreeMap::new();
+    let mut rewarded: HashSet<&str> = HashSet::new();
+    for tx in &block.transactions {
+        if !is_coinbase(tx) {
+            if !rewarded.is_empty() {
+                return Err(format!("Transaction {} comes after the coinbase", tx.txid));
+            }
+            let total = network_fees.entry(&tx.network).or_default();
+            *total = total.checked_add(tx.fee)?;
+            continue;
+        }
+        // Tanpa ini coinbase bisa pakai txid transaksi pending dan bikin transaksi itu kelihatan udah masuk
+        if tx.nonce != block.index || tx.txid != encoding::transaction_id(tx) {
+            return Err(format!("Coinbase {} must have nonce {} and the txid of its payload", tx.txid, block.index));
+        }
+        if !rewarded.insert(&tx.network) {
+            return Err(format!("Coinbase {} is a second {} reward in the block", tx.txid, tx.network));
+        }
+        let fees = network_fees.get(tx.network.as_str()).copied().unwrap_or_default();
+        let allowed = block_reward(spec, block.index, &tx.network, fees);
+        if tx.amount > allowed {
+            return Err(format!("Coinbase {} mints {} {}, more than the allowed reward {}", tx.txid, tx.amount, tx.network, allowed));
+        }
+    }
+    Ok(())
+}
+
+// Transaksi dengan `lock_until` cuma boleh masuk block yang height/median time past-nya udah lewat lock-nya
+pub fn check_lock(tx: &Transaction, height: u64, median_time: u64) -> Result<(), TxError> {
+    match tx.lock_until {
+        Some(lock) if !tx.is_mature(height, median_time) => Err(TxError::Invalid(format!("Transaction {} is locked until {}", tx.txid, lock))),
+        _ => Ok(()),
+    }
+}
+
+// Apply block secara atomik: kalau satu transaksi gagal, wallet ga berubah sama sekali.
+// `median_time` = median time past dari ancestor block ini, buat cek time lock.
+pub fn apply_block(wallet: &mut Wallet, block: &Block, spec: &ChainSpec, median_time: u64) -> Result<(), String> {
+    check_coinbase(block, spec).map_err(|e| format!("Block #{}: {}", block.index, e))?;
+    let mut next = wallet.clone();
+    for tx in &block.transactions {
+        check_lock(tx, block.index, median_time)
+            .and_then(|_| apply_transaction(&mut next, tx, spec))
+            .map_err(|e| format!("Block #{} tx {}: {}", block.index, tx.txid, e))?;
+    }
+    *wallet = next;
+    Ok(())
+}
+
+// Kebalikan `apply_block`, dipakai waktu rollback cabang lama pas reorg
+pub fn revert_block(wallet: &mut Wallet, block: &Block) -> Result<(), String> {
+    let mut next = wallet.clone();
+    for change in block_changes(block)?.iter().rev() {
+        change.inverse().apply(&mut next)?;
+    }
+    for tx in block.transactions.iter().rev().filter(|tx| !is_coinbase(tx)) {
+        next.set_nonce(&tx.from, tx.nonce);
+    }
+    *wallet = next;
+    Ok(())
+}
+
+// Bangun ulang semua saldo dari genesis
+pub fn replay(chain: &[Block], spec: &ChainSpec) -> Result<Wallet, String> {
+    let mut wallet = Wallet::new();
+    for (height, block) in chain.iter().enumerate() {
+        apply_block(&mut wallet, block, spec, validation::median_time_past(&chain[..height]))?;
+    }
+    Ok(wallet)
+}
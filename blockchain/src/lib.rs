@@ -1,32 +1,100 @@
-use std::collections::HashMap;
-use sha2::{Digest, Sha256};
-use rand::Rng;
-use std::fs::{File, OpenOptions};
-use std::io::{Read, Write};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use wallet::encryption::{self, Keypair};
+
+pub use wallet::amount::{self, Amount};
+pub use wallet::asset::{self, Asset, AssetRegistry};
+pub use wallet::multisig::{self, MultisigPolicy, MultisigWitness, PartialSignature};
+pub use wallet::peg::{self, Peg};
 use serde::{Serialize, Deserialize};
 
+// Kapan transaksi boleh masuk block: mulai height tertentu, atau kalau median time past
+// (lihat `validation::median_time_past`) udah sampai waktu tertentu (unix detik). Di JSON: {"height": 120} atau {"time": 1700000000}.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockTime {
+    Height(u64),
+    Time(u64),
+}
+
+impl LockTime {
+    pub fn is_mature(&self, height: u64, timestamp: u64) -> bool {
+        match *self {
+            LockTime::Height(h) => height >= h,
+            LockTime::Time(t) => timestamp >= t,
+        }
+    }
+}
+
+impl fmt::Display for LockTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LockTime::Height(h) => write!(f, "height {}", h),
+            LockTime::Time(t) => write!(f, "time {}", t),
+        }
+    }
+}
+
+// Kenapa transaksi ditolak mempool, ledger, atau pool multisig. Jenisnya dipakai API buat milih
+// kode error (lihat `error::ErrorCode`), isinya pesan buat dibaca manusia.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxError {
+    // Melanggar aturan chain: nonce, peg, fee, lock, supply cap, dll
+    Invalid(String),
+    InsufficientBalance(String),
+    // Tanda tangan (atau tanda tangan multisig) ga ada, salah, atau dobel
+    InvalidSignature(String),
+    // Transaksi yang sama, atau nonce yang sama dari sender itu, udah ada
+    Conflict(String),
+    // Faucet: alamat atau IP ini masih kena cooldown
+    RateLimited(String),
+}
+
+impl fmt::Display for TxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TxError::Invalid(m) | TxError::InsufficientBalance(m) | TxError::InvalidSignature(m) | TxError::Conflict(m) | TxError::RateLimited(m) => write!(f, "{}", m),
+        }
+    }
+}
+
+impl std::error::Error for TxError {}
+
+impl From<TxError> for String {
+    fn from(e: TxError) -> Self {
+        e.to_string()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
     pub from: String,
     pub to: String,
-    pub amount: f64,
-    pub peg_value: String,
+    pub amount: Amount,
+    pub peg_value: Peg,
     pub network: String,
-    pub fee: f64,
+    pub fee: Amount,
     pub signature: Option<String>,
-    pub status: String,
+    pub status: TxStatus,
     pub txid: String,
     pub timestamp: u64,
+    // Urutan transaksi dari `from` (0, 1, 2, ...), ikut ditandatangani. Coinbase pakai height block-nya.
+    #[serde(default)]
+    pub nonce: u64,
+    // Ditahan di mempool sampai lock-nya lewat, ikut ditandatangani
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_until: Option<LockTime>,
+    // Gantinya `signature` buat transaksi dari address multisig: policy plus tanda tangan yang terkumpul.
+    // Ga ikut payload (policy udah terikat lewat `from`), jadi txid tetap sama selama tanda tangan dikumpulin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multisig: Option<MultisigWitness>,
 }
 
 impl Transaction {
-    pub fn new(from: String, to: String, amount: f64, peg_value: String, network: String, fee: f64) -> Self {
-        let mut hasher = Sha256::new();
-        let nonce: u64 = rand::thread_rng().random();
-        let data = format!("{}{}{}{}{}{}{}", from, to, amount, peg_value, network, fee, nonce);
-        hasher.update(data.as_bytes());
-        let txid = format!("{:x}", hasher.finalize());
-        Transaction {
+    pub fn new(from: String, to: String, amount: Amount, peg_value: Peg, network: String, fee: Amount) -> Self {
+        let mut tx = Transaction {
             from,
             to,
             amount,
@@ -34,160 +102,819 @@ impl Transaction {
             network,
             fee,
             signature: None,
-            status: "pending".to_string(),
-            txid,
+            status: TxStatus::Pending,
+            txid: String::new(),
             timestamp: chrono::Utc::now().timestamp() as u64,
+            nonce: 0,
+            lock_until: None,
+            multisig: None,
+        };
+        tx.txid = encoding::transaction_id(&tx);
+        tx
+    }
+
+    pub fn with_nonce(mut self, nonce: u64) -> Self {
+        self.nonce = nonce;
+        self.txid = encoding::transaction_id(&self);
+        self
+    }
+
+    pub fn with_lock(mut self, lock_until: LockTime) -> Self {
+        self.lock_until = Some(lock_until);
+        self.txid = encoding::transaction_id(&self);
+        self
+    }
+
+    // Boleh masuk block di `height` dengan `timestamp`?
+    pub fn is_mature(&self, height: u64, timestamp: u64) -> bool {
+        self.lock_until.is_none_or(|lock| lock.is_mature(height, timestamp))
+    }
+
+    // Buat transaksi dari `policy.address()`, tanda tangannya ditambah lewat `sign_multisig`
+    pub fn with_multisig(mut self, policy: MultisigPolicy) -> Self {
+        self.multisig = Some(MultisigWitness::new(policy));
+        self
+    }
+
+    // Tanda tangan sender atas payload kanonik (termasuk nonce); txid = hash payload itu,
+    // jadi txid ga bisa diganti buat ngirim ulang transaksi yang sama
+    pub fn sign(&mut self, keypair: &Keypair) {
+        self.signature = Some(encryption::sign(keypair, &encoding::transaction_payload(self)));
+        self.txid = encoding::transaction_id(self);
+    }
+
+    // Tanda tangan salah satu signer multisig, boleh dipanggil berkali-kali sampai threshold
+    pub fn sign_multisig(&mut self, keypair: &Keypair) -> Result<(), String> {
+        let payload = encoding::transaction_payload(self);
+        let witness = self.multisig.as_mut().ok_or_else(|| format!("Transaction {} is not a multisig transaction", self.txid))?;
+        witness.sign(keypair, &payload)
+    }
+
+    pub fn verify_signature(&self) -> Result<(), String> {
+        if let Some(witness) = &self.multisig {
+            if self.signature.is_some() {
+                return Err(format!("Multisig transaction {} must not carry a single signature", self.txid));
+            }
+            witness
+                .verify(&self.from, &encoding::transaction_payload(self))
+                .map_err(|e| format!("Invalid multisig on transaction {}: {}", self.txid, e))?;
+            return self.check_txid();
         }
+        if multisig::is_multisig_address(&self.from) {
+            return Err(format!("Transaction {} from multisig {} has no multisig signatures", self.txid, self.from));
+        }
+        let signature = self
+            .signature
+            .as_deref()
+            .ok_or_else(|| format!("Transaction {} from {} is not signed", self.txid, self.from))?;
+        encryption::verify(&self.from, &encoding::transaction_payload(self), signature)
+            .map_err(|e| format!("Invalid signature on transaction {}: {}", self.txid, e))?;
+        self.check_txid()
+    }
+
+    fn check_txid(&self) -> Result<(), String> {
+        if self.txid != encoding::transaction_id(self) {
+            return Err(format!("Transaction id {} does not match its signed payload", self.txid));
+        }
+        Ok(())
     }
 
-    pub fn validate(&self) -> Result<(), String> {
-        if self.amount <= 0.0 || self.fee < 0.0 {
-            Err("Invalid amount or fee".to_string())
-        } else {
-            Ok(())
+    // Yang keluar dari saldo sender: amount + fee
+    pub fn total_debit(&self) -> Result<Amount, String> {
+        self.amount.checked_add(self.fee)
+    }
+
+    pub fn validate(&self) -> Result<(), TxError> {
+        if self.amount.is_zero() {
+            return Err(TxError::Invalid("Amount must be greater than zero".to_string()));
+        }
+        self.total_debit().map_err(TxError::Invalid)?;
+        // Mint dari coinbase ga punya pemilik, selain itu wajib ditandatangani sender
+        if !ledger::is_coinbase(self) {
+            self.verify_signature().map_err(TxError::InvalidSignature)?;
         }
+        Ok(())
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Saldo in-memory, sumbernya cuma chain (lihat `ledger`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Wallet {
-    balances: HashMap<String, HashMap<String, f64>>,
+    balances: HashMap<String, HashMap<String, Amount>>,
+    // Nonce berikutnya yang diharapkan per address
+    #[serde(default)]
+    nonces: HashMap<String, u64>,
+    // Total saldo per network, buat ngecek supply cap
+    #[serde(default)]
+    supply: HashMap<String, Amount>,
 }
 
 impl Wallet {
     pub fn new() -> Self {
-        let mut balances = load_wallet_balances();
-        if balances.is_empty() || !balances.contains_key("genesis") {
-            balances.insert("genesis".to_string(), HashMap::from([("SOL".to_string(), 100.0)]));
-            if let Err(e) = save_wallet_balances(&balances) {
-                eprintln!("Failed to save initial balances: {}", e);
-            }
-        }
-        Wallet { balances }
+        Self::default()
     }
 
-    pub fn get_balance(&self, address: &str, network: &str) -> f64 {
-        self.balances.get(address).and_then(|n| n.get(network)).copied().unwrap_or(0.0)
+    pub fn get_balance(&self, address: &str, network: &str) -> Amount {
+        self.balances.get(address).and_then(|n| n.get(network)).copied().unwrap_or_default()
     }
 
-    pub fn update_balance(&mut self, address: &str, network: &str, amount: f64) {
-        let entry = self.balances.entry(address.to_string()).or_insert(HashMap::new());
-        *entry.entry(network.to_string()).or_insert(0.0) += amount;
-        if let Err(e) = save_wallet_balances(&self.balances) {
-            eprintln!("Error saving balances: {}", e);
-        }
+    pub fn credit(&mut self, address: &str, network: &str, amount: Amount) -> Result<(), String> {
+        let balance = self.get_balance(address, network).checked_add(amount)?;
+        let supply = self.total_supply(network).checked_add(amount)?;
+        self.balances.entry(address.to_string()).or_default().insert(network.to_string(), balance);
+        self.supply.insert(network.to_string(), supply);
+        Ok(())
     }
-}
 
-fn load_wallet_balances() -> HashMap<String, HashMap<String, f64>> {
-    match File::open("wallet_balances.json") {
-        Ok(mut file) => {
-            let mut contents = String::new();
-            if file.read_to_string(&mut contents).is_ok() {
-                serde_json::from_str(&contents).unwrap_or_default()
-            } else {
-                HashMap::new()
-            }
-        }
-        Err(_) => HashMap::new(),
+    // Gagal kalau saldo kurang, saldo ga pernah minus
+    pub fn debit(&mut self, address: &str, network: &str, amount: Amount) -> Result<(), String> {
+        let balance = self.get_balance(address, network);
+        let remaining = balance.checked_sub(amount).map_err(|_| {
+            format!("Insufficient balance for {}: has {} {}, needs {}", address, balance, network, amount)
+        })?;
+        self.balances.entry(address.to_string()).or_default().insert(network.to_string(), remaining);
+        self.supply.insert(network.to_string(), self.total_supply(network).saturating_sub(amount));
+        Ok(())
+    }
+
+    // Semua saldo address, urut per network
+    pub fn balances(&self, address: &str) -> BTreeMap<String, Amount> {
+        self.balances.get(address).map(|b| b.iter().map(|(n, a)| (n.clone(), *a)).collect()).unwrap_or_default()
     }
-}
 
-fn save_wallet_balances(balances: &HashMap<String, HashMap<String, f64>>) -> Result<(), std::io::Error> {
-    let json = serde_json::to_string_pretty(balances)?;
-    let mut file = OpenOptions::new().write(true).truncate(true).create(true).open("wallet_balances.json")?;
-    file.write_all(json.as_bytes())?;
-    Ok(())
+    pub fn total_supply(&self, network: &str) -> Amount {
+        self.supply.get(network).copied().unwrap_or_default()
+    }
+
+    pub fn get_nonce(&self, address: &str) -> u64 {
+        self.nonces.get(address).copied().unwrap_or(0)
+    }
+
+    pub fn set_nonce(&mut self, address: &str, nonce: u64) {
+        self.nonces.insert(address.to_string(), nonce);
+    }
 }
 
+pub mod chainspec;
+pub mod difficulty;
+pub mod encoding;
+pub mod error;
+pub mod explorer;
+pub mod faucet;
+pub mod fees;
+pub mod forkchoice;
+pub mod history;
+pub mod index;
+pub mod ledger;
+pub mod mempool;
+pub mod merkle;
+pub mod miner;
 pub mod mining;
 pub mod network;
+pub mod oracle;
+pub mod signing;
+pub mod status;
+pub mod storage;
+pub mod validation;
+
+use chainspec::{ChainSpec, CHAINSPEC_FILE};
+use faucet::Faucet;
+use forkchoice::{BlockOutcome, BlockTree, ChainTip, ReorgEvent};
+use index::ChainIndex;
+use mempool::{Mempool, MAX_BLOCK_TRANSACTIONS};
+use miner::MinerState;
+use oracle::PriceOracle;
+use signing::{SigningPool, SigningStatus};
+use status::{TxLog, TxStatus};
+use storage::BlockStore;
+use validation::ChainValidationError;
 
 #[derive(Debug, Clone)]
 pub struct Blockchain {
     pub chain: Vec<Block>,
-    pub difficulty: usize,
+    // Difficulty buat block berikutnya, di-retarget tiap block
+    pub difficulty: u64,
     pub target_block_time: u64,
     pub wallet: Wallet,
-    pub history: Vec<Transaction>,
-    pub network_fees: HashMap<String, f64>,
+    // txid, address, dan hash block di chain aktif, lihat `index`
+    pub index: ChainIndex,
+    pub mempool: Mempool,
+    pub spec: ChainSpec,
+    // Harga fiat buat ngecek peg, diisi `oracle::spawn_price_feed` atau POST /admin/oracle/prices
+    pub oracle: PriceOracle,
+    // Transaksi multisig yang tanda tangannya belum cukup
+    pub signing: SigningPool,
+    // Transaksi yang ditolak atau kadaluarsa, biar alasannya bisa ditanya
+    pub tx_log: TxLog,
+    // Cuma ada di network dev/test kalau diaktifkan lewat env, lihat `Faucet::from_env`
+    pub faucet: Option<Faucet>,
+    // Di-share sama thread miner: progress mining dan sinyal buat batalin percobaan yang lagi jalan
+    pub miner: Arc<MinerState>,
+    tree: BlockTree,
+    store: Option<BlockStore>,
 }
 
 impl Blockchain {
-    pub fn new() -> Self {
-        let blockchain = Blockchain {
-            chain: vec![],
-            difficulty: 2,
-            target_block_time: 10,
-            wallet: Wallet::new(),
-            history: vec![],
-            network_fees: HashMap::from([
-                ("SOL".to_string(), 0.1),
-                ("BTC".to_string(), 0.001),
-                ("ETH".to_string(), 0.01),
-                ("BNB".to_string(), 2.0),
-            ]),
-        };
+    // Spec diambil dari `chainspec.json` di `data_dir` kalau ada
+    pub fn open(data_dir: impl AsRef<Path>) -> Result<Self, std::io::Error> {
+        let spec = ChainSpec::load_or_default(data_dir.as_ref().join(CHAINSPEC_FILE)).map_err(invalid_data)?;
+        Self::open_with_spec(data_dir, spec)
+    }
+
+    // Semua block (termasuk cabang samping) di-load dari block store di `data_dir`,
+    // chain aktif = cabang dengan work terbesar. Saldo dan history dibangun ulang dari situ.
+    // Store kosong diisi genesis dari spec; store dengan genesis lain ditolak.
+    pub fn open_with_spec(data_dir: impl AsRef<Path>, spec: ChainSpec) -> Result<Self, std::io::Error> {
+        let store = BlockStore::open(data_dir)?;
+        let stored = store.load()?;
+        let mut blockchain = Self::with_parts(spec, Some(store.clone()));
+        let genesis_hash = blockchain.chain[0].hash.clone();
+        match stored.first() {
+            None => store.append(&blockchain.chain[0])?,
+            Some(first) if first.hash != genesis_hash => {
+                return Err(invalid_data(format!(
+                    "Stored genesis {} does not match chainspec genesis {}",
+                    first.hash, genesis_hash
+                )));
+            }
+            Some(_) => {
+                // Sama kayak runtime: tip cuma ganti kalau work-nya lebih besar (first seen menang)
+                let mut best = (genesis_hash.clone(), blockchain.tree.work(&genesis_hash));
+                for block in stored.into_iter().skip(1) {
+                    let hash = block.hash.clone();
+                    let work = blockchain.tree.insert(block).map_err(invalid_data)?;
+                    if work > best.1 {
+                        best = (hash, work);
+                    }
+                }
+                blockchain.chain = blockchain.tree.branch(&best.0);
+            }
+        }
+        blockchain.rebuild_state().map_err(invalid_data)?;
+        println!("Loaded {} blocks from disk (genesis {})", blockchain.chain.len(), blockchain.chain[0].hash);
+        Ok(blockchain)
+    }
+
+    pub fn in_memory(spec: ChainSpec) -> Self {
+        let mut blockchain = Self::with_parts(spec, None);
+        if let Err(e) = blockchain.rebuild_state() {
+            eprintln!("Invalid genesis allocations: {}", e);
+        }
         blockchain
     }
 
+    // Folder block store, None buat chain in-memory
+    pub fn data_dir(&self) -> Option<&Path> {
+        self.store.as_ref().map(|store| store.path().parent().unwrap_or_else(|| Path::new(".")))
+    }
+
+    pub fn rebuild_state(&mut self) -> Result<(), String> {
+        self.wallet = ledger::replay(&self.chain, &self.spec)?;
+        self.difficulty = difficulty::next_difficulty(&self.chain, &self.spec);
+        self.restore_index();
+        Ok(())
+    }
+
+    // Snapshot index dari disk dipakai kalau tip-nya masih ada di chain aktif, tinggal
+    // ditambah block sesudahnya. Kalau ga cocok (misal abis reorg) dibangun ulang dari genesis.
+    fn restore_index(&mut self) {
+        let snapshot = self.store.as_ref().and_then(|store| store.load_index()).and_then(|index| {
+            let height = index.block_height(index.tip())?;
+            (self.chain.get(height as usize)?.hash == index.tip()).then_some((index, height))
+        });
+        let tip = self.chain.last().map(|b| b.hash.as_str()).unwrap_or_default();
+        match snapshot {
+            Some((index, _)) if index.tip() == tip => self.index = index,
+            Some((mut index, height)) => {
+                for block in &self.chain[height as usize + 1..] {
+                    index.add_block(block);
+                }
+                self.index = index;
+                self.persist_index();
+            }
+            None => {
+                self.index = ChainIndex::build(&self.chain);
+                self.persist_index();
+            }
+        }
+    }
+
+    fn with_parts(spec: ChainSpec, store: Option<BlockStore>) -> Self {
+        let genesis = spec.genesis_block();
+        Blockchain {
+            tree: BlockTree::new(genesis.clone()),
+            chain: vec![genesis],
+            difficulty: spec.difficulty,
+            target_block_time: spec.target_block_time,
+            wallet: Wallet::new(),
+            index: ChainIndex::default(),
+            mempool: Mempool::default(),
+            spec,
+            oracle: PriceOracle::default(),
+            signing: SigningPool::default(),
+            tx_log: TxLog::default(),
+            faucet: None,
+            miner: Arc::new(MinerState::default()),
+            store,
+        }
+    }
+
+    pub fn validate_chain(&self) -> Result<(), ChainValidationError> {
+        validation::validate_blocks(&self.chain, &self.spec)
+    }
+
+    // Tambah atau ganti aset di registry. Balikin true kalau asetnya baru.
+    pub fn upsert_asset(&mut self, asset: Asset) -> Result<bool, String> {
+        let mut spec = self.spec.clone();
+        let created = spec.assets.upsert(asset)?.is_none();
+        self.update_spec(spec)?;
+        Ok(created)
+    }
+
+    pub fn remove_asset(&mut self, symbol: &str) -> Result<Asset, String> {
+        let mut spec = self.spec.clone();
+        let removed = spec.assets.remove(symbol).ok_or_else(|| format!("Unknown asset {}", symbol))?;
+        self.update_spec(spec)?;
+        Ok(removed)
+    }
+
+    // Registry ikut aturan konsensus, jadi perubahan cuma diterima kalau chain yang udah ada
+    // tetap valid. Transaksi mempool yang jadi ga valid dibuang, spec baru ditulis ke data dir.
+    fn update_spec(&mut self, spec: ChainSpec) -> Result<(), String> {
+        validation::validate_blocks(&self.chain, &spec).map_err(|e| format!("Registry change would invalidate the chain: {}", e))?;
+        if let Some(data_dir) = self.data_dir() {
+            spec.save(data_dir.join(CHAINSPEC_FILE))?;
+        }
+        self.spec = spec;
+        let invalid: Vec<String> = self
+            .mempool
+            .transactions()
+            .into_iter()
+            .filter(|tx| ledger::check_transaction(tx, &self.spec).is_err())
+            .map(|tx| tx.txid)
+            .collect();
+        for txid in invalid {
+            if let Some(tx) = self.mempool.remove(&txid) {
+                let reason = ledger::check_transaction(&tx, &self.spec).err().map(|e| e.to_string()).unwrap_or_default();
+                self.reject(tx, format!("Removed from mempool after registry change: {}", reason));
+            }
+        }
+        self.miner.request_abort();
+        Ok(())
+    }
+
+    // Block dan posisi transaksi di chain aktif
+    pub fn chain_transaction(&self, txid: &str) -> Option<(&Block, usize)> {
+        let location = self.index.transaction(txid)?;
+        let block = self.chain.get(location.block as usize)?;
+        Some((block, location.position))
+    }
+
+    // Transaksi di chain aktif yang dikirim atau diterima `address`, urut dari yang paling lama
+    pub fn address_history(&self, address: &str) -> Vec<Transaction> {
+        self.index
+            .address_transactions(address)
+            .iter()
+            .filter_map(|txid| self.chain_transaction(txid))
+            .map(|(block, position)| history_entry(&block.transactions[position], block))
+            .collect()
+    }
+
+    // Status terkini: di chain (included/confirmed tergantung kedalaman), di mempool atau
+    // nunggu tanda tangan multisig (pending), atau tercatat ditolak/kadaluarsa
+    pub fn transaction_status(&self, txid: &str) -> Option<TxStatus> {
+        let tip = self.chain.len().saturating_sub(1) as u64;
+        if let Some((block, _)) = self.chain_transaction(txid) {
+            return Some(TxStatus::in_chain(block.index, tip));
+        }
+        if self.mempool.contains(txid) {
+            return Some(TxStatus::Pending);
+        }
+        if let Some(tx) = self.tx_log.get(txid) {
+            return Some(tx.status.clone());
+        }
+        self.signing.get(txid).map(|_| TxStatus::Pending)
+    }
+
+    // Transaksi dari mana pun asalnya, dengan `status` dari `transaction_status`
+    pub fn find_transaction(&self, txid: &str) -> Option<Transaction> {
+        let mut tx = self
+            .chain_transaction(txid)
+            .map(|(block, position)| &block.transactions[position])
+            .or_else(|| self.mempool.get(txid))
+            .or_else(|| self.tx_log.get(txid))
+            .or_else(|| self.signing.get(txid))
+            .cloned()?;
+        tx.status = self.transaction_status(txid)?;
+        Some(tx)
+    }
+
+    fn reject(&mut self, tx: Transaction, reason: String) {
+        println!("Rejected tx {}: {}", tx.txid, reason);
+        self.tx_log.record(tx, TxStatus::Rejected { reason });
+    }
+
+    pub fn transaction_proof(&self, txid: &str) -> Option<(BlockHeader, merkle::MerkleProof)> {
+        let (block, position) = self.chain_transaction(txid)?;
+        let proof = merkle::build_proof(&block.transactions, position)?;
+        Some((block.header(), proof))
+    }
+
+    // Validasi (termasuk peg vs harga oracle) lalu taruh di mempool; masuk block nanti lewat miner atau `produce_block`.
+    // Percobaan mining yang lagi jalan dibatalin biar template berikutnya ikut bawa transaksi ini.
+    // Yang ditolak dicatat di `tx_log` beserta alasannya.
+    pub fn submit_transaction(&mut self, tx: Transaction) -> Result<(), TxError> {
+        if ledger::is_coinbase(&tx) {
+            return Err(TxError::Invalid(format!("Coinbase transaction {} cannot be submitted", tx.txid)));
+        }
+        if self.index.transaction(&tx.txid).is_some() {
+            return Err(TxError::Conflict(format!("Transaction {} is already in the chain", tx.txid)));
+        }
+        if self.mempool.contains(&tx.txid) {
+            return Err(TxError::Conflict(format!("Transaction {} is already in the mempool", tx.txid)));
+        }
+        let txid = tx.txid.clone();
+        let result = self
+            .oracle
+            .verify_peg(&tx, &self.spec.assets, now())
+            .map_err(TxError::Invalid)
+            .and_then(|_| self.mempool.insert(tx.clone(), &self.wallet, &self.spec, now()));
+        if let Err(e) = result {
+            self.reject(tx, e.to_string());
+            return Err(e);
+        }
+        self.tx_log.remove(&txid);
+        self.miner.request_abort();
+        Ok(())
+    }
+
+    // Mulai ngumpulin tanda tangan buat transaksi multisig. Peg dan fee dicek dari awal biar signer
+    // ga tanda tangan transaksi yang pasti ditolak. Begitu tanda tangan cukup langsung masuk mempool.
+    pub fn propose_multisig(&mut self, tx: Transaction) -> Result<SigningStatus, TxError> {
+        self.spec.assets.check_peg(&tx.network, &tx.peg_value).map_err(TxError::Invalid)?;
+        fees::check(&self.spec.assets, &tx).map_err(TxError::Invalid)?;
+        self.oracle.verify_peg(&tx, &self.spec.assets, now()).map_err(TxError::Invalid)?;
+        let txid = self.signing.propose(tx)?.txid.clone();
+        self.submit_if_signed(&txid)
+    }
+
+    // Kirim dana faucet ke `to`. Cooldown per address dan per IP baru dicatat kalau transfernya
+    // diterima mempool.
+    pub fn request_faucet(&mut self, to: &str, ip: Option<std::net::IpAddr>) -> Result<Transaction, TxError> {
+        let faucet = self.faucet.as_ref().ok_or_else(|| TxError::Invalid("Faucet is disabled".to_string()))?;
+        let now = now();
+        faucet.check_recipient(to).map_err(TxError::Invalid)?;
+        faucet.check(to, ip, now).map_err(TxError::RateLimited)?;
+        let nonce = self.mempool.next_nonce(&self.wallet, faucet.address());
+        let size = fees::transaction_size(&faucet.transfer(to, &self.spec, nonce, Amount::ZERO));
+        let fee = self.spec.fees.estimate(&self.spec.assets, faucet.network(), size, self.mempool.len()).map_err(TxError::Invalid)?.recommended_fee;
+        let tx = faucet.transfer(to, &self.spec, nonce, fee);
+        self.submit_transaction(tx.clone())?;
+        if let Some(faucet) = self.faucet.as_mut() {
+            faucet.record(to, ip, now);
+        }
+        println!("Faucet sent {} {} to {} in tx {}", self.spec.assets.display_amount(tx.amount, &tx.network), tx.network, to, tx.txid);
+        Ok(tx)
+    }
+
+    pub fn sign_multisig(&mut self, txid: &str, signature: PartialSignature) -> Result<SigningStatus, TxError> {
+        self.signing.sign(txid, signature)?;
+        self.submit_if_signed(txid)
+    }
+
+    // Kalau mempool nolak, tanda tangannya tetap disimpan di pool
+    fn submit_if_signed(&mut self, txid: &str) -> Result<SigningStatus, TxError> {
+        let tx = self.signing.get(txid).cloned().ok_or_else(|| TxError::Invalid(format!("No multisig transaction {} is collecting signatures", txid)))?;
+        if !tx.multisig.as_ref().is_some_and(|w| w.is_complete()) {
+            return Ok(SigningStatus::of(&tx, false));
+        }
+        self.submit_transaction(tx.clone())?;
+        self.signing.remove(txid);
+        println!("Multisig tx {} has enough signatures, added to mempool", txid);
+        Ok(SigningStatus::of(&tx, true))
+    }
+
+    fn pending_selection(&mut self) -> Vec<Transaction> {
+        let height = self.chain.len() as u64;
+        for tx in self.mempool.expire(now(), height) {
+            println!("Expired tx {} from mempool", tx.txid);
+            self.tx_log.record(tx, TxStatus::Expired);
+        }
+        let median_time = validation::median_time_past(&self.chain);
+        self.mempool.select(&self.wallet, &self.spec, height, median_time, MAX_BLOCK_TRANSACTIONS)
+    }
+
+    // Block kandidat (belum di-mining) di atas tip sekarang, buat miner background.
+    // None kalau mempool kosong.
+    pub fn block_template(&mut self) -> Option<Block> {
+        self.miner.clear_abort();
+        let transactions = self.pending_selection();
+        if transactions.is_empty() {
+            return None;
+        }
+        Some(self.candidate_block(transactions))
+    }
+
+    // Rakit block dari mempool dan mining langsung di thread ini.
+    // Balikin height block baru, atau None kalau mempool kosong.
+    pub fn produce_block(&mut self) -> Option<u64> {
+        let transactions = self.pending_selection();
+        if transactions.is_empty() {
+            return None;
+        }
+        let height = self.chain.len() as u64;
+        self.add_block(transactions);
+        (self.chain.len() as u64 > height).then_some(height)
+    }
+
     pub fn add_block(&mut self, transactions: Vec<Transaction>) {
+        let candidate = self.candidate_block(transactions);
+        let block = mining::mine_candidate(candidate, miner::DEFAULT_WORKERS, &MinerState::default())
+            .expect("mining without abort always finds a nonce");
+        if let Err(e) = self.accept_block(block) {
+            eprintln!("Mined block rejected: {}", e);
+        }
+    }
+
+    fn candidate_block(&self, transactions: Vec<Transaction>) -> Block {
+        // Transaksi yang bakal bikin saldo minus dibuang dulu sebelum mining.
+        // Status final di-set sebelum block di-mining.
+        let mut pending = self.wallet.clone();
+        let index = self.chain.len() as u64;
+        let median_time = validation::median_time_past(&self.chain);
+        let transactions: Vec<Transaction> = transactions
+            .into_iter()
+            .filter(|tx| match ledger::check_lock(tx, index, median_time).and_then(|_| ledger::apply_transaction(&mut pending, tx, &self.spec)) {
+                Ok(()) => true,
+                Err(e) => {
+                    println!("Dropping tx {}: {}", tx.txid, e);
+                    false
+                }
+            })
+            .map(|mut tx| {
+                tx.status = TxStatus::Included { block: index };
+                tx
+            })
+            .collect();
+
         let previous_hash = self.chain.last().map(|b| b.hash.clone()).unwrap_or_else(|| String::from("0"));
-        let mut block = Block::new(self.chain.len() as u64, transactions.clone(), previous_hash);
-        block = crate::mining::mine_block(block.index as u32, block.previous_hash.clone(), block.transactions.clone(), self.difficulty);
+        let previous_timestamp = self.chain.last().map(|b| b.timestamp).unwrap_or_default();
+        mining::build_candidate(index, previous_hash, previous_timestamp, transactions, self.difficulty, &self.spec, &pending)
+    }
+
+    pub fn tips(&self) -> Vec<ChainTip> {
+        self.tree.tips()
+    }
+
+    pub fn block_by_hash(&self, hash: &str) -> Option<&Block> {
+        self.chain.get(self.index.block_height(hash)? as usize)
+    }
+
+    pub fn total_work(&self) -> u128 {
+        self.chain.last().map(|b| self.tree.work(&b.hash)).unwrap_or(0)
+    }
+
+    // Terima block dari mana aja (miner lokal atau peer). Block yang nyambung ke tip langsung
+    // di-append; block di cabang lain disimpan di tree, dan kalau cabang itu work-nya lebih
+    // besar dari chain aktif, chain di-reorg ke cabang tersebut.
+    pub fn accept_block(&mut self, block: Block) -> Result<BlockOutcome, String> {
+        if self.tree.contains(&block.hash) {
+            return Ok(BlockOutcome::AlreadyKnown);
+        }
+        if !self.tree.contains(&block.previous_hash) {
+            return Err(format!("Unknown parent {} for block #{}", block.previous_hash, block.index));
+        }
+        let tip_hash = self.chain.last().map(|b| b.hash.clone()).unwrap_or_default();
+        // Nyambung ke tip: ancestor-nya ya chain aktif, tree cuma ditelusuri buat cabang samping
+        let side_branch = (block.previous_hash != tip_hash).then(|| self.tree.branch(&block.previous_hash));
+        let ancestors = side_branch.as_deref().unwrap_or(&self.chain);
+        let expected_difficulty = difficulty::next_difficulty(ancestors, &self.spec);
+        let max_timestamp = now().saturating_add(validation::max_future_drift(&self.spec));
+        validation::validate_header(&block, ancestors.last(), expected_difficulty, max_timestamp).map_err(|kind| {
+            ChainValidationError { block_index: block.index, block_hash: block.hash.clone(), kind }.to_string()
+        })?;
+        let Some(mut branch) = side_branch else {
+            return self.extend_tip(block);
+        };
+
+        // Cabang samping: saldo di cabang itu harus valid juga sebelum block disimpan
+        branch.push(block.clone());
+        ledger::replay(&branch, &self.spec)?;
+        let work = self.tree.insert(block.clone())?;
+        self.persist(&block);
+        if work <= self.tree.work(&tip_hash) {
+            println!("Side branch block #{} {} (work {})", block.index, block.hash, work);
+            return Ok(BlockOutcome::SideBranch { height: block.index, work });
+        }
+        self.miner.request_abort();
+        Ok(BlockOutcome::Reorganized(self.reorganize(branch)))
+    }
+
+    fn extend_tip(&mut self, block: Block) -> Result<BlockOutcome, String> {
+        let median_time = validation::median_time_past(&self.chain);
+        ledger::apply_block(&mut self.wallet, &block, &self.spec, median_time)?;
+        self.tree.insert(block.clone())?;
+        self.persist(&block);
+        let height = block.index;
+        self.mempool.remove_included(&block);
+        self.reject_stale();
+        self.index.add_block(&block);
         self.chain.push(block);
+        self.difficulty = difficulty::next_difficulty(&self.chain, &self.spec);
+        if height.is_multiple_of(INDEX_SNAPSHOT_INTERVAL) {
+            self.persist_index();
+        }
+        // Tip berubah: template yang lagi di-mining udah basi
+        self.miner.request_abort();
+        Ok(BlockOutcome::Extended { height })
+    }
 
-        if let Some(last_block) = self.chain.last_mut() {
-            for tx in &mut last_block.transactions {
-                if tx.status == "pending" {
-                    tx.status = "berhasil".to_string();
-                }
+    // Rollback block di chain aktif sampai titik fork, lalu apply block dari cabang baru
+    fn reorganize(&mut self, new_branch: Vec<Block>) -> ReorgEvent {
+        let fork_height = self
+            .chain
+            .iter()
+            .zip(&new_branch)
+            .take_while(|(a, b)| a.hash == b.hash)
+            .count()
+            - 1;
+        let old_blocks = self.chain.split_off(fork_height + 1);
+        let new_blocks = &new_branch[fork_height + 1..];
+
+        let applied = old_blocks
+            .iter()
+            .rev()
+            .try_for_each(|block| ledger::revert_block(&mut self.wallet, block))
+            .and_then(|_| {
+                new_branch.iter().enumerate().skip(fork_height + 1).try_for_each(|(height, block)| {
+                    ledger::apply_block(&mut self.wallet, block, &self.spec, validation::median_time_past(&new_branch[..height]))
+                })
+            });
+        self.chain.extend(new_blocks.iter().cloned());
+        if let Err(e) = applied {
+            eprintln!("Reorg apply failed, replaying from genesis: {}", e);
+            match ledger::replay(&self.chain, &self.spec) {
+                Ok(wallet) => self.wallet = wallet,
+                Err(e) => eprintln!("Replay after reorg failed: {}", e),
             }
         }
+        self.difficulty = difficulty::next_difficulty(&self.chain, &self.spec);
 
-        for block in &self.chain {
-            for tx in &block.transactions {
-                if !self.history.iter().any(|h| h.txid == tx.txid) {
-                    self.history.push(tx.clone());
-                    if tx.status == "berhasil" {
-                        let fee = self.network_fees.get(&tx.network).unwrap_or(&0.0);
-                        self.wallet.update_balance(&tx.from, &tx.network, -(tx.amount + *fee));
-                        self.wallet.update_balance(&tx.to, &tx.network, tx.amount);
-                    }
-                }
+        let new_txids: HashSet<&str> = new_blocks.iter().flat_map(|b| &b.transactions).map(|tx| tx.txid.as_str()).collect();
+        let dropped: Vec<Transaction> = old_blocks
+            .iter()
+            .flat_map(|b| &b.transactions)
+            .filter(|tx| !ledger::is_coinbase(tx) && !new_txids.contains(tx.txid.as_str()))
+            .cloned()
+            .collect();
+        for block in old_blocks.iter().rev() {
+            self.index.remove_block(block);
+        }
+        for block in new_blocks {
+            self.index.add_block(block);
+        }
+        self.persist_index();
+
+        // Transaksi dari cabang lama balik ke mempool kalau masih valid di chain baru
+        for block in new_blocks {
+            self.mempool.remove_included(block);
+        }
+        for tx in &dropped {
+            let mut tx = tx.clone();
+            tx.status = TxStatus::Pending;
+            if let Err(e) = self.mempool.insert(tx.clone(), &self.wallet, &self.spec, now()) {
+                self.reject(tx, format!("Dropped by reorg and no longer valid: {}", e));
+            }
+        }
+        self.reject_stale();
+
+        let event = ReorgEvent {
+            depth: old_blocks.len() as u64,
+            fork_height: fork_height as u64,
+            old_tip: old_blocks.last().map(|b| b.hash.clone()).unwrap_or_default(),
+            new_tip: self.chain.last().map(|b| b.hash.clone()).unwrap_or_default(),
+            dropped,
+        };
+        println!(
+            "Chain reorg at height {}: depth {}, {} -> {}, dropped {} txs {:?}",
+            event.fork_height,
+            event.depth,
+            event.old_tip,
+            event.new_tip,
+            event.dropped.len(),
+            event.dropped.iter().map(|tx| tx.txid.as_str()).collect::<Vec<_>>()
+        );
+        event
+    }
+
+    // Transaksi mempool yang nonce-nya keburu dipakai transaksi lain di chain
+    fn reject_stale(&mut self) {
+        for tx in self.mempool.prune_stale(&self.wallet) {
+            let reason = format!("Nonce {} for {} was already used on chain", tx.nonce, tx.from);
+            self.reject(tx, reason);
+        }
+    }
+
+    fn persist_index(&self) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_index(&self.index) {
+                eprintln!("Failed to persist chain index: {}", e);
+            }
+        }
+    }
+
+    fn persist(&self, block: &Block) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.append(block) {
+                eprintln!("Failed to persist block #{}: {}", block.index, e);
             }
         }
     }
 }
 
-#[derive(Debug, Clone)]
+// Snapshot index ditulis tiap sekian block (dan tiap reorg); sisanya dikejar dari block store waktu start
+pub const INDEX_SNAPSHOT_INTERVAL: u64 = 100;
+
+// Lock chain yang di-share API, miner, dan oracle. Kalau ada thread yang panic sambil megang lock,
+// state-nya tetap dipakai (tiap perubahan chain udah divalidasi sebelum ditulis) daripada ikut panic.
+pub fn lock_chain(blockchain: &Mutex<Blockchain>) -> MutexGuard<'_, Blockchain> {
+    blockchain.lock().unwrap_or_else(|poisoned| {
+        eprintln!("Blockchain lock was poisoned by a panicked thread, recovering");
+        blockchain.clear_poison();
+        poisoned.into_inner()
+    })
+}
+
+fn now() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+// Salinan transaksi buat history, status-nya sesuai block yang memuatnya
+fn history_entry(tx: &Transaction, block: &Block) -> Transaction {
+    let mut tx = tx.clone();
+    tx.status = TxStatus::Included { block: block.index };
+    tx
+}
+
+fn invalid_data(e: String) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Block {
     pub index: u64,
     pub timestamp: u64,
     pub transactions: Vec<Transaction>,
     pub previous_hash: String,
+    pub merkle_root: String,
+    pub hash: String,
+    pub nonce: u64,
+    pub difficulty: u64,
+}
+
+// Header doang tanpa transaksi, cukup buat light client verifikasi merkle proof
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockHeader {
+    pub index: u64,
+    pub timestamp: u64,
+    pub previous_hash: String,
+    pub merkle_root: String,
     pub hash: String,
     pub nonce: u64,
+    pub difficulty: u64,
 }
 
 impl Block {
     pub fn new(index: u64, transactions: Vec<Transaction>, previous_hash: String) -> Self {
         let timestamp = chrono::Utc::now().timestamp() as u64;
+        let merkle_root = merkle::merkle_root(&transactions);
         let mut block = Block {
             index,
             timestamp,
             transactions,
             previous_hash,
+            merkle_root,
             hash: String::new(),
             nonce: 0,
+            difficulty: 1,
         };
         block.calculate_hash();
         block
     }
 
+    pub fn header(&self) -> BlockHeader {
+        BlockHeader {
+            index: self.index,
+            timestamp: self.timestamp,
+            previous_hash: self.previous_hash.clone(),
+            merkle_root: self.merkle_root.clone(),
+            hash: self.hash.clone(),
+            nonce: self.nonce,
+            difficulty: self.difficulty,
+        }
+    }
+
     pub fn calculate_hash(&mut self) {
-        let mut hasher = Sha256::new();
-        hasher.update(format!("{}{:?}{}", self.index, self.transactions, self.previous_hash).as_bytes());
-        self.hash = format!("{:x}", hasher.finalize());
+        self.hash = encoding::block_hash(self);
     }
-}
\ No newline at end of file
+}
@@ -1,21 +1,65 @@
 use actix_web::{web, App, HttpServer, HttpResponse};
 use std::sync::{Arc, Mutex};
-use blockchain::{Blockchain, network::add_transaction, network::add_single_transaction, network::get_wallet, network::get_history, network::get_transaction};
+use blockchain::{error, Blockchain, network::get_block, network::get_block_by_hash, network::get_latest_block, network::get_blocks, network::get_chain_info, network::search, network::add_transaction, network::add_single_transaction, network::get_wallet, network::get_history, network::get_transaction, network::get_transaction_proof, network::validate_chain, network::submit_block, network::get_chain_tips, network::mine_pending, network::get_mempool, network::get_mining_status, network::get_nonce, network::get_fee_estimate, network::get_assets, network::get_asset, network::upsert_asset, network::remove_asset, network::get_prices, network::push_prices, network::create_multisig_address, network::propose_multisig, network::get_multisig, network::sign_multisig, network::get_faucet, network::request_faucet, faucet::Faucet, miner::{spawn_miner, MinerConfig}, oracle::{spawn_price_feed, OracleConfig}};
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    let blockchain = Arc::new(Mutex::new(Blockchain::new()));
+    // Data node di folder kerja. Block store rusak harus dibenerin dulu, jangan diam-diam mulai dari genesis.
+    let mut chain = Blockchain::open(".").unwrap_or_else(|e| {
+        eprintln!("Failed to open chain data: {}", e);
+        std::process::exit(1);
+    });
+    chain.faucet = Faucet::from_env(&chain.spec);
+    if let Some(faucet) = &chain.faucet {
+        println!("Faucet enabled at {}", faucet.address());
+    }
+    let blockchain = Arc::new(Mutex::new(chain));
+    let miner_config = MinerConfig::from_env();
+    println!("Starting background miner with {} workers", miner_config.workers);
+    spawn_miner(blockchain.clone(), miner_config);
+    spawn_price_feed(blockchain.clone(), OracleConfig::from_env());
     println!("Starting Actix Web server on http://localhost:8080...");
 
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(blockchain.clone()))
+            .app_data(error::json_config())
+            .app_data(error::query_config())
+            .app_data(error::path_config())
             .service(add_transaction)
             .service(add_single_transaction)
             .service(get_wallet)
+            .service(get_nonce)
+            .service(get_fee_estimate)
+            .service(get_assets)
+            .service(get_asset)
+            .service(upsert_asset)
+            .service(remove_asset)
+            .service(get_prices)
+            .service(push_prices)
+            .service(create_multisig_address)
+            .service(propose_multisig)
+            .service(get_multisig)
+            .service(sign_multisig)
+            .service(get_faucet)
+            .service(request_faucet)
             .service(get_history)
             .service(get_transaction)
+            .service(get_transaction_proof)
+            .service(validate_chain)
+            .service(submit_block)
+            .service(get_chain_tips)
+            .service(get_chain_info)
+            .service(get_blocks)
+            .service(get_latest_block)
+            .service(get_block_by_hash)
+            .service(get_block)
+            .service(search)
+            .service(mine_pending)
+            .service(get_mempool)
+            .service(get_mining_status)
             .route("/", web::get().to(index))
+            .default_service(web::to(error::route_not_found))
     })
     .bind(("127.0.0.1", 8080))?
     .run()
@@ -0,0 +1,193 @@
+use crate::chainspec::ChainSpec;
+use crate::{ledger, Amount, Block, Transaction, TxError, Wallet};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+pub const DEFAULT_MAX_AGE_SECS: u64 = 3600;
+pub const DEFAULT_CAPACITY: usize = 10_000;
+pub const MAX_BLOCK_TRANSACTIONS: usize = 500;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MempoolEntry {
+    pub tx: Transaction,
+    pub received_at: u64,
+}
+
+// Transaksi yang udah divalidasi tapi belum masuk block
+#[derive(Debug, Clone)]
+pub struct Mempool {
+    entries: HashMap<String, MempoolEntry>,
+    max_age_secs: u64,
+    capacity: usize,
+}
+
+impl Default for Mempool {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_AGE_SECS, DEFAULT_CAPACITY)
+    }
+}
+
+impl Mempool {
+    pub fn new(max_age_secs: u64, capacity: usize) -> Self {
+        Mempool { entries: HashMap::new(), max_age_secs, capacity }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, txid: &str) -> bool {
+        self.entries.contains_key(txid)
+    }
+
+    pub fn get(&self, txid: &str) -> Option<&Transaction> {
+        self.entries.get(txid).map(|e| &e.tx)
+    }
+
+    pub fn transactions(&self) -> Vec<Transaction> {
+        self.ordered().into_iter().map(|e| e.tx.clone()).collect()
+    }
+
+    // Saldo yang masih bisa dipakai: saldo di chain dikurangi pengeluaran yang masih pending
+    pub fn available_balance(&self, wallet: &Wallet, address: &str, network: &str) -> Amount {
+        let debits = self
+            .entries
+            .values()
+            .filter(|e| e.tx.network == network && e.tx.from == address)
+            .fold(Amount::ZERO, |total, e| total.saturating_add(e.tx.total_debit().unwrap_or(e.tx.amount)));
+        wallet.get_balance(address, network).saturating_sub(debits)
+    }
+
+    // Nonce berikutnya buat `address`: nonce di chain, atau lanjutan dari transaksi yang masih pending
+    pub fn next_nonce(&self, wallet: &Wallet, address: &str) -> u64 {
+        self.entries
+            .values()
+            .filter(|e| e.tx.from == address)
+            .map(|e| e.tx.nonce + 1)
+            .fold(wallet.get_nonce(address), u64::max)
+    }
+
+    // Coinbase cuma dibuat miner di dalam block, ga pernah lewat mempool
+    pub fn insert(&mut self, tx: Transaction, wallet: &Wallet, spec: &ChainSpec, now: u64) -> Result<(), TxError> {
+        if ledger::is_coinbase(&tx) {
+            return Err(TxError::Invalid(format!("Coinbase transaction {} cannot be submitted", tx.txid)));
+        }
+        if self.entries.contains_key(&tx.txid) {
+            return Err(TxError::Conflict(format!("Transaction {} is already in the mempool", tx.txid)));
+        }
+        ledger::check_transaction(&tx, spec)?;
+        spec.fees.check_admission(&spec.assets, &tx, self.entries.len()).map_err(TxError::Invalid)?;
+        let confirmed = wallet.get_nonce(&tx.from);
+        if tx.nonce < confirmed {
+            return Err(TxError::Invalid(format!("Nonce {} for {} is already used, next is {}", tx.nonce, tx.from, confirmed)));
+        }
+        if self.entries.values().any(|e| e.tx.from == tx.from && e.tx.nonce == tx.nonce) {
+            return Err(TxError::Conflict(format!("Nonce {} for {} is already pending", tx.nonce, tx.from)));
+        }
+        let next = self.next_nonce(wallet, &tx.from);
+        if tx.nonce > next {
+            return Err(TxError::Invalid(format!("Nonce {} for {} skips ahead, next is {}", tx.nonce, tx.from, next)));
+        }
+        let available = self.available_balance(wallet, &tx.from, &tx.network);
+        let needed = tx.total_debit().map_err(TxError::Invalid)?;
+        if available < needed {
+            return Err(TxError::InsufficientBalance(format!(
+                "Insufficient balance for {}: {} {} available after pending transactions, needs {}",
+                tx.from, available, tx.network, needed
+            )));
+        }
+        if self.entries.len() >= self.capacity {
+            // Penuh: buang fee paling kecil, tapi cuma kalau yang baru bayar lebih mahal
+            let lowest = self.ordered().last().map(|e| (e.tx.txid.clone(), e.tx.fee));
+            match lowest {
+                Some((txid, fee)) if fee < tx.fee => {
+                    self.entries.remove(&txid);
+                }
+                _ => return Err(TxError::Invalid("Mempool is full".to_string())),
+            }
+        }
+        self.entries.insert(tx.txid.clone(), MempoolEntry { tx, received_at: now });
+        Ok(())
+    }
+
+    pub fn remove(&mut self, txid: &str) -> Option<Transaction> {
+        self.entries.remove(txid).map(|e| e.tx)
+    }
+
+    pub fn remove_included(&mut self, block: &Block) {
+        for tx in &block.transactions {
+            self.entries.remove(&tx.txid);
+        }
+    }
+
+    // Buang transaksi yang nonce-nya udah kepake di chain (misal konflik sama transaksi lain
+    // dengan nonce yang sama)
+    pub fn prune_stale(&mut self, wallet: &Wallet) -> Vec<Transaction> {
+        let stale: Vec<String> = self
+            .entries
+            .values()
+            .filter(|e| e.tx.nonce < wallet.get_nonce(&e.tx.from))
+            .map(|e| e.tx.txid.clone())
+            .collect();
+        stale.iter().filter_map(|txid| self.remove(txid)).collect()
+    }
+
+    // Transaksi yang masih dikunci di `next_height` ditahan, umurnya baru dihitung setelah lock-nya lewat
+    pub fn expire(&mut self, now: u64, next_height: u64) -> Vec<Transaction> {
+        for entry in self.entries.values_mut().filter(|e| !e.tx.is_mature(next_height, now)) {
+            entry.received_at = now;
+        }
+        let max_age = self.max_age_secs;
+        let expired: Vec<String> = self
+            .entries
+            .values()
+            .filter(|e| now.saturating_sub(e.received_at) > max_age)
+            .map(|e| e.tx.txid.clone())
+            .collect();
+        expired.iter().filter_map(|txid| self.remove(txid)).collect()
+    }
+
+    // Fee tertinggi duluan, kalau fee sama yang masuk duluan menang
+    fn ordered(&self) -> Vec<&MempoolEntry> {
+        let mut entries: Vec<&MempoolEntry> = self.entries.values().collect();
+        entries.sort_by(|a, b| {
+            b.tx.fee
+                .cmp(&a.tx.fee)
+                .then(a.received_at.cmp(&b.received_at))
+                .then_with(|| a.tx.txid.cmp(&b.tx.txid))
+        });
+        entries
+    }
+
+    // Pilih transaksi buat block berikutnya (di `height`, time lock dicek ke `median_time`) sesuai urutan fee.
+    // Transaksi yang belum bisa di-apply (misal nunggu transfer lain di mempool)
+    // dicoba lagi di putaran berikutnya, jadi dependensi di dalam satu block tetap urut.
+    // Yang masih dikunci dilewat, begitu juga transaksi sesudahnya dari sender yang sama (nonce-nya nyambung).
+    pub fn select(&self, wallet: &Wallet, spec: &ChainSpec, height: u64, median_time: u64, max: usize) -> Vec<Transaction> {
+        let mut scratch = wallet.clone();
+        let mut remaining: Vec<&Transaction> = self.ordered().into_iter().map(|e| &e.tx).collect();
+        let mut selected = Vec::new();
+        let mut included: HashSet<String> = HashSet::new();
+        loop {
+            let before = selected.len();
+            for tx in &remaining {
+                if selected.len() >= max {
+                    break;
+                }
+                if tx.is_mature(height, median_time) && ledger::apply_transaction(&mut scratch, tx, spec).is_ok() {
+                    included.insert(tx.txid.clone());
+                    selected.push((*tx).clone());
+                }
+            }
+            remaining.retain(|tx| !included.contains(&tx.txid));
+            if selected.len() == before || selected.len() >= max || remaining.is_empty() {
+                break;
+            }
+        }
+        selected
+    }
+}
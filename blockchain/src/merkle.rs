@@ -0,0 +1,148 @@
+use crate::difficulty::meets_target;
+use crate::encoding;
+use crate::{BlockHeader, Transaction};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+// Leaf dan node pakai prefix beda biar leaf ga bisa dipalsuin jadi node internal.
+// Node tanpa pasangan dinaikin apa adanya (ga diduplikat kayak Bitcoin).
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+pub const EMPTY_ROOT: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Left,
+    Right,
+}
+
+// `side` = posisi sibling relatif ke hash yang lagi dihitung
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofStep {
+    pub hash: String,
+    pub side: Side,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    pub txid: String,
+    pub tx_hash: String,
+    pub position: usize,
+    pub steps: Vec<ProofStep>,
+}
+
+pub fn transaction_hash(tx: &Transaction) -> String {
+    encoding::sha256_hex(&encoding::encode_transaction(tx))
+}
+
+fn leaf(tx_hash: &str) -> Option<[u8; 32]> {
+    let bytes = decode_hash(tx_hash)?;
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(bytes);
+    Some(hasher.finalize().into())
+}
+
+fn node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn decode_hash(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut out = [0u8; 32];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(out)
+}
+
+fn encode_hash(bytes: &[u8; 32]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node(left, right),
+            [single] => *single,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+fn leaves(transactions: &[Transaction]) -> Vec<[u8; 32]> {
+    transactions
+        .iter()
+        .map(|tx| leaf(&transaction_hash(tx)).expect("sha256 hex is always 32 bytes"))
+        .collect()
+}
+
+pub fn merkle_root(transactions: &[Transaction]) -> String {
+    let mut level = leaves(transactions);
+    if level.is_empty() {
+        return EMPTY_ROOT.to_string();
+    }
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    encode_hash(&level[0])
+}
+
+pub fn build_proof(transactions: &[Transaction], position: usize) -> Option<MerkleProof> {
+    let tx = transactions.get(position)?;
+    let mut level = leaves(transactions);
+    let mut index = position;
+    let mut steps = Vec::new();
+    while level.len() > 1 {
+        let sibling = index ^ 1;
+        if sibling < level.len() {
+            let side = if sibling < index { Side::Left } else { Side::Right };
+            steps.push(ProofStep { hash: encode_hash(&level[sibling]), side });
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    Some(MerkleProof {
+        txid: tx.txid.clone(),
+        tx_hash: transaction_hash(tx),
+        position,
+        steps,
+    })
+}
+
+pub fn proof_root(proof: &MerkleProof) -> Option<String> {
+    let mut current = leaf(&proof.tx_hash)?;
+    for step in &proof.steps {
+        let sibling = decode_hash(&step.hash)?;
+        current = match step.side {
+            Side::Left => node(&sibling, &current),
+            Side::Right => node(&current, &sibling),
+        };
+    }
+    Some(encode_hash(&current))
+}
+
+// Buat light client: cukup header + proof, ga perlu isi block.
+// Header dicek dulu (hash dan proof-of-work), baru proof-nya dicocokin ke merkle_root.
+pub fn verify_proof(proof: &MerkleProof, header: &BlockHeader) -> bool {
+    if encoding::header_hash(header) != header.hash {
+        return false;
+    }
+    if header.index > 0 && !meets_target(&header.hash, header.difficulty) {
+        return false;
+    }
+    proof_root(proof).is_some_and(|root| root == header.merkle_root)
+}
+
+pub fn verify_inclusion(tx: &Transaction, proof: &MerkleProof, header: &BlockHeader) -> bool {
+    transaction_hash(tx) == proof.tx_hash && verify_proof(proof, header)
+}
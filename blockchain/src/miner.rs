@@ -0,0 +1,192 @@
+use crate::mining;
+use crate::{lock_chain, Blockchain};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+pub const DEFAULT_WORKERS: usize = 4;
+pub const WORKERS_ENV: &str = "MINER_WORKERS";
+
+#[derive(Debug, Clone)]
+pub struct MinerConfig {
+    pub workers: usize,
+    // Seberapa sering cek mempool kalau lagi ga ada yang di-mining
+    pub poll_interval: Duration,
+}
+
+impl Default for MinerConfig {
+    fn default() -> Self {
+        MinerConfig { workers: DEFAULT_WORKERS, poll_interval: Duration::from_secs(1) }
+    }
+}
+
+impl MinerConfig {
+    // Jumlah worker bisa di-override lewat env MINER_WORKERS
+    pub fn from_env() -> Self {
+        let mut config = MinerConfig::default();
+        match std::env::var(WORKERS_ENV).map(|v| v.parse::<usize>()) {
+            Ok(Ok(workers)) if workers > 0 => config.workers = workers,
+            Ok(_) => eprintln!("Invalid {}, using {} workers", WORKERS_ENV, DEFAULT_WORKERS),
+            Err(_) => {}
+        }
+        config
+    }
+}
+
+// State miner yang di-share antara thread mining, `Blockchain`, dan handler HTTP.
+// Dibaca tanpa nunggu lock chain, jadi status mining tetap bisa dicek selama mining.
+#[derive(Debug, Default)]
+pub struct MinerState {
+    enabled: AtomicBool,
+    running: AtomicBool,
+    abort: AtomicBool,
+    wake: AtomicBool,
+    workers: AtomicUsize,
+    height: AtomicU64,
+    nonce: AtomicU64,
+    hashes: AtomicU64,
+    thread_hashes: Mutex<Vec<u64>>,
+    started_at_ms: AtomicU64,
+    blocks_mined: AtomicU64,
+    attempts_aborted: AtomicU64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MiningStatus {
+    pub enabled: bool,
+    pub running: bool,
+    pub workers: usize,
+    pub height: u64,
+    pub current_nonce: u64,
+    pub hashes: u64,
+    pub thread_hashes: Vec<u64>,
+    pub elapsed_ms: u64,
+    pub hashrate: f64,
+    pub blocks_mined: u64,
+    pub attempts_aborted: u64,
+}
+
+impl MinerState {
+    pub fn request_abort(&self) {
+        self.abort.store(true, Ordering::Relaxed);
+    }
+
+    pub fn abort_requested(&self) -> bool {
+        self.abort.load(Ordering::Relaxed)
+    }
+
+    // Dipanggil pas template baru dirakit (masih di bawah lock chain)
+    pub fn clear_abort(&self) {
+        self.abort.store(false, Ordering::Relaxed);
+    }
+
+    // Minta miner background langsung ngecek mempool tanpa nunggu poll berikutnya
+    pub fn wake(&self) {
+        self.wake.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn begin(&self, height: u64, workers: usize) {
+        self.height.store(height, Ordering::Relaxed);
+        self.workers.store(workers, Ordering::Relaxed);
+        self.nonce.store(0, Ordering::Relaxed);
+        self.hashes.store(0, Ordering::Relaxed);
+        *self.thread_hashes.lock().unwrap_or_else(|e| e.into_inner()) = vec![0; workers.max(1)];
+        self.started_at_ms.store(now_ms(), Ordering::Relaxed);
+        self.running.store(true, Ordering::Relaxed);
+    }
+
+    // Thread mining ngelapor per batch, bukan per hash, biar ga rebutan atomic
+    pub fn record_hashes(&self, thread: usize, count: u64, nonce: u64) {
+        self.nonce.store(nonce, Ordering::Relaxed);
+        self.hashes.fetch_add(count, Ordering::Relaxed);
+        let mut thread_hashes = self.thread_hashes.lock().unwrap_or_else(|e| e.into_inner());
+        if thread >= thread_hashes.len() {
+            thread_hashes.resize(thread + 1, 0);
+        }
+        thread_hashes[thread] += count;
+    }
+
+    pub fn finish(&self, mined: bool) {
+        self.running.store(false, Ordering::Relaxed);
+        if mined {
+            self.blocks_mined.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.attempts_aborted.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn status(&self) -> MiningStatus {
+        let elapsed_ms = now_ms().saturating_sub(self.started_at_ms.load(Ordering::Relaxed));
+        let hashes = self.hashes.load(Ordering::Relaxed);
+        MiningStatus {
+            enabled: self.is_enabled(),
+            running: self.running.load(Ordering::Relaxed),
+            workers: self.workers.load(Ordering::Relaxed),
+            height: self.height.load(Ordering::Relaxed),
+            current_nonce: self.nonce.load(Ordering::Relaxed),
+            hashes,
+            thread_hashes: self.thread_hashes.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+            elapsed_ms,
+            hashrate: if elapsed_ms > 0 { hashes as f64 * 1000.0 / elapsed_ms as f64 } else { 0.0 },
+            blocks_mined: self.blocks_mined.load(Ordering::Relaxed),
+            attempts_aborted: self.attempts_aborted.load(Ordering::Relaxed),
+        }
+    }
+
+    fn take_wake(&self) -> bool {
+        self.wake.swap(false, Ordering::Relaxed)
+    }
+}
+
+fn now_ms() -> u64 {
+    chrono::Utc::now().timestamp_millis() as u64
+}
+
+// Miner jalan di thread sendiri. Lock chain cuma dipegang sebentar buat ngerakit template
+// dan nerima block hasilnya; nyari nonce-nya di luar lock, jadi API tetap jalan selama mining.
+pub fn spawn_miner(blockchain: Arc<Mutex<Blockchain>>, config: MinerConfig) -> thread::JoinHandle<()> {
+    let state = lock_chain(&blockchain).miner.clone();
+    state.enabled.store(true, Ordering::Relaxed);
+    state.workers.store(config.workers, Ordering::Relaxed);
+    thread::spawn(move || loop {
+        let candidate = lock_chain(&blockchain).block_template();
+        let Some(candidate) = candidate else {
+            let mut waited = Duration::ZERO;
+            while waited < config.poll_interval && !state.take_wake() {
+                let step = Duration::from_millis(50);
+                thread::sleep(step);
+                waited += step;
+            }
+            continue;
+        };
+        let height = candidate.index;
+        match mining::mine_candidate_with_stats(candidate, config.workers, &state) {
+            Some((block, stats)) => {
+                let mut blockchain = lock_chain(&blockchain);
+                match blockchain.accept_block(block) {
+                    Ok(outcome) => {
+                        println!(
+                            "Miner: block #{} accepted {:?} ({} txs left in mempool)",
+                            height,
+                            outcome,
+                            blockchain.mempool.len()
+                        );
+                        // Chain in-memory ga punya data dir, stats-nya ga disimpan
+                        if let Some(dir) = blockchain.data_dir() {
+                            mining::save_mining_stats(&dir.join(mining::MINING_STATS_FILE), stats)
+                                .unwrap_or_else(|e| eprintln!("Failed to save mining stats: {}", e));
+                        }
+                    }
+                    Err(e) => eprintln!("Miner: mined block #{} rejected: {}", height, e),
+                }
+            }
+            None => println!("Miner: attempt at height {} aborted, rebuilding template", height),
+        }
+    })
+}
@@ -1,41 +1,44 @@
-use sha2::{Digest, Sha256};
-use crate::Block;
+use crate::{encoding, merkle};
+use crate::{Amount, Block, Wallet};
+use crate::chainspec::ChainSpec;
+use crate::miner::{MinerState, DEFAULT_WORKERS};
 use crate::Transaction;
+use crate::ledger::{self, COINBASE_ADDRESS};
+use crate::status::TxStatus;
+use crate::difficulty::meets_target;
 use chrono::Utc;
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
 use std::thread;
-use std::time::SystemTime;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
 use std::fs::{File, OpenOptions};
 use std::io::{Write, Read};
+use std::path::Path;
 use serde::{Serialize, Deserialize};
 
-// Tambah struct buat mining stats
-#[derive(Serialize, Deserialize, Debug)]
-struct MiningStats {
-    block_index: u64,
-    timestamp: u64,
-    nonce: u64,
-    hash: String,
-    mining_time_sec: u64,
-    difficulty: usize,
-    estimated_hashrate: f64,
+pub const MINING_STATS_FILE: &str = "mining_stats.json";
+
+// Statistik satu block hasil mining. Cuma ditulis miner background ke data dir node
+// (lihat `save_mining_stats`), `mine_block`/`add_block` ga nyentuh file apa pun.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MiningStats {
+    pub block_index: u64,
+    pub timestamp: u64,
+    pub nonce: u64,
+    pub hash: String,
+    pub mining_time_sec: f64,
+    pub difficulty: u64,
+    // Hash per detik; record lama bisa null
+    pub estimated_hashrate: Option<f64>,
+    #[serde(default)]
+    pub total_hashes: u64,
+    #[serde(default)]
+    pub thread_hashes: Vec<u64>,
 }
 
-pub fn calculate_hash(index: u32, timestamp: i64, transactions: &Vec<Transaction>, previous_hash: &str, nonce: u64) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(index.to_string());
-    hasher.update(timestamp.to_string());
-    for tx in transactions {
-        hasher.update(format!("{:?}", tx));
-    }
-    hasher.update(previous_hash);
-    hasher.update(nonce.to_string());
-    format!("{:x}", hasher.finalize())
-}
-
-fn load_mining_stats() -> Vec<MiningStats> {
-    match File::open("mining_stats.json") {
+fn load_mining_stats(path: &Path) -> Vec<MiningStats> {
+    match File::open(path) {
         Ok(mut file) => {
             let mut contents = String::new();
             if file.read_to_string(&mut contents).is_ok() {
@@ -48,106 +51,187 @@ fn load_mining_stats() -> Vec<MiningStats> {
     }
 }
 
-fn save_mining_stats(stats: &[MiningStats]) -> Result<(), std::io::Error> {
-    let json = serde_json::to_string_pretty(stats)?;
-    let mut file = OpenOptions::new().write(true).truncate(true).create(true).open("mining_stats.json")?;
+// Tambah satu record ke file stats di `path`
+pub fn save_mining_stats(path: &Path, stats: MiningStats) -> Result<(), std::io::Error> {
+    let mut all = load_mining_stats(path);
+    all.push(stats);
+    let json = serde_json::to_string_pretty(&all)?;
+    let mut file = OpenOptions::new().write(true).truncate(true).create(true).open(path)?;
     file.write_all(json.as_bytes())?;
-    println!("Mining stats saved to mining_stats.json"); // Tambah logging
+    println!("Mining stats saved to {}", path.display());
     Ok(())
 }
 
-use crate::{load_wallet_balances, save_wallet_balances};
-
-pub fn mine_block(index: u32, previous_hash: String, mut transactions: Vec<Transaction>, difficulty: usize) -> Block {
-    let base_reward = 50.0;
-    let halving_interval = 100_000;
-    let halvings = index / halving_interval;
-    let adjusted_reward = base_reward / 2f64.powf(halvings as f64);
-
-    let mut network_rewards: HashMap<String, f64> = HashMap::new();
+// Block kandidat siap di-mining (reward miner udah ditambah, nonce belum dicari).
+// Reward per network = setengah total fee + subsidi dari chainspec (udah kena halving),
+// dipotong kalau bakal nembus supply cap. `wallet` = saldo setelah transaksi block ini di-apply.
+// Timestamp-nya ga pernah mundur dari parent, walaupun parent dari peer yang jam-nya lebih cepat.
+pub fn build_candidate(
+    index: u64,
+    previous_hash: String,
+    previous_timestamp: u64,
+    mut transactions: Vec<Transaction>,
+    difficulty: u64,
+    spec: &ChainSpec,
+    wallet: &Wallet,
+) -> Block {
+    let mut network_fees: BTreeMap<String, Amount> = BTreeMap::new();
     for tx in &transactions {
-        *network_rewards.entry(tx.network.clone()).or_insert(0.0) += tx.fee * 0.5;
+        let total = network_fees.entry(tx.network.clone()).or_default();
+        *total = total.saturating_add(tx.fee);
     }
 
-    let peg_values: HashMap<String, String> = network_rewards.iter()
-        .map(|(k, v)| (k.clone(), format!("{} {}", v + adjusted_reward, k)))
-        .collect();
-
-    for (network, reward_total) in network_rewards.iter() {
-        transactions.push(Transaction {
-            from: "network".to_string(),
+    for (network, fees) in network_fees {
+        let mut reward = ledger::block_reward(spec, index, &network, fees);
+        if let Some(cap) = spec.assets.get(&network).and_then(|asset| asset.supply_cap) {
+            reward = reward.min(cap.saturating_sub(wallet.total_supply(&network)));
+        }
+        if reward.is_zero() {
+            continue;
+        }
+        let mut coinbase = Transaction {
+            from: COINBASE_ADDRESS.to_string(),
             to: "miner_address".to_string(),
-            amount: reward_total + adjusted_reward,
-            peg_value: peg_values.get(network).unwrap().clone(),
+            amount: reward,
+            peg_value: spec.assets.native_peg(reward, &network),
             network: network.clone(),
-            fee: 0.0,
+            fee: Amount::ZERO,
             signature: None,
-            status: "pending".to_string(),
-            txid: format!("reward_{}_{}", network, Utc::now().timestamp()),
+            status: TxStatus::Included { block: index },
+            txid: String::new(),
             timestamp: Utc::now().timestamp() as u64,
-        });
+            nonce: index,
+            lock_until: None,
+            multisig: None,
+        };
+        // Height ikut di payload lewat nonce, jadi txid coinbase ga bisa bentrok antar block
+        coinbase.txid = encoding::transaction_id(&coinbase);
+        transactions.push(coinbase);
     }
 
-    let timestamp = Utc::now().timestamp();
-    let found = Arc::new(Mutex::new(None));
-    let threads: Vec<_> = (0..4).map(|i| {
-        let found = Arc::clone(&found);
-        let txs = transactions.clone();
-        let prev_hash = previous_hash.clone();
-        thread::spawn(move || {
-            let mut local_nonce = i * 1_000_000;
-            loop {
-                if found.lock().unwrap().is_some() {
-                    break;
-                }
-                let hash = calculate_hash(index, timestamp, &txs, &prev_hash, local_nonce);
-                if hash.starts_with(&"0".repeat(difficulty)) {
-                    *found.lock().unwrap() = Some((hash, local_nonce));
-                    break;
-                }
-                local_nonce += 1;
-            }
-        })
-    }).collect();
+    let timestamp = (Utc::now().timestamp() as u64).max(previous_timestamp);
+    Block {
+        index,
+        timestamp,
+        merkle_root: merkle::merkle_root(&transactions),
+        transactions,
+        previous_hash,
+        hash: String::new(),
+        nonce: 0,
+        difficulty,
+    }
+}
+
+pub fn mine_block(
+    index: u32,
+    previous_hash: String,
+    previous_timestamp: u64,
+    transactions: Vec<Transaction>,
+    difficulty: u64,
+    spec: &ChainSpec,
+    wallet: &Wallet,
+) -> Block {
+    let candidate = build_candidate(index as u64, previous_hash, previous_timestamp, transactions, difficulty, spec, wallet);
+    mine_candidate(candidate, DEFAULT_WORKERS, &MinerState::default()).expect("mining without abort always finds a nonce")
+}
+
+// Tiap thread nge-flush counter lokalnya ke `MinerState` tiap segini hash
+const PROGRESS_EVERY: u64 = 4096;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchOutcome {
+    Found { hash: String, nonce: u64 },
+    // Semua nonce 0..=max_nonce udah dicoba, header harus diganti (timestamp di-roll)
+    Exhausted,
+    Aborted,
+}
 
-    for t in threads {
-        t.join().unwrap();
+// Satu putaran pencarian nonce buat header `prefix`. Thread `i` nyoba nonce i, i+workers,
+// i+2*workers, ... sampai `max_nonce`, jadi ga ada nonce yang dicoba dua kali.
+pub fn search_nonces(prefix: &[u8], difficulty: u64, workers: usize, max_nonce: u64, state: &MinerState) -> SearchOutcome {
+    let workers = workers.max(1) as u64;
+    let done = AtomicBool::new(false);
+    let found = Mutex::new(None);
+    thread::scope(|s| {
+        for i in 0..workers {
+            let (done, found) = (&done, &found);
+            s.spawn(move || {
+                let mut nonce = Some(i).filter(|n| *n <= max_nonce);
+                let mut pending = 0;
+                while let Some(current) = nonce {
+                    if done.load(Ordering::Relaxed) || state.abort_requested() {
+                        break;
+                    }
+                    let hash = encoding::hash_with_nonce(prefix, current);
+                    pending += 1;
+                    if meets_target(&hash, difficulty) {
+                        done.store(true, Ordering::Relaxed);
+                        found.lock().unwrap().get_or_insert((hash, current));
+                        break;
+                    }
+                    if pending == PROGRESS_EVERY {
+                        state.record_hashes(i as usize, pending, current);
+                        pending = 0;
+                    }
+                    nonce = current.checked_add(workers).filter(|n| *n <= max_nonce);
+                }
+                state.record_hashes(i as usize, pending, nonce.unwrap_or(max_nonce));
+            });
+        }
+    });
+    match found.into_inner().unwrap() {
+        Some((hash, nonce)) => SearchOutcome::Found { hash, nonce },
+        None if state.abort_requested() => SearchOutcome::Aborted,
+        None => SearchOutcome::Exhausted,
     }
+}
 
-    let start = SystemTime::now();
-    let (hash, nonce) = found.lock().unwrap().clone().unwrap();
-    let duration = start.elapsed().unwrap().as_secs();
+// Cari nonce buat block kandidat pakai `workers` thread. Balikin None kalau dibatalin lewat
+// `state.request_abort()` (ada transaksi baru atau block dari peer), progress di-update ke `state`.
+// Kalau ruang nonce habis, timestamp dimajuin biar header-nya beda lalu dicari lagi dari 0.
+pub fn mine_candidate(block: Block, workers: usize, state: &MinerState) -> Option<Block> {
+    mine_candidate_with_stats(block, workers, state).map(|(block, _)| block)
+}
 
+// Sama kayak `mine_candidate`, plus statistik mining-nya buat disimpan pemanggil
+pub fn mine_candidate_with_stats(mut block: Block, workers: usize, state: &MinerState) -> Option<(Block, MiningStats)> {
+    let difficulty = block.difficulty;
+    let start = Instant::now();
+    state.begin(block.index, workers);
+    let (hash, nonce) = loop {
+        // Header di-encode sekali per putaran, tiap percobaan cuma ganti nonce di belakang
+        let prefix = encoding::header_prefix(&block.header());
+        match search_nonces(&prefix, difficulty, workers, u64::MAX, state) {
+            SearchOutcome::Found { hash, nonce } => break (hash, nonce),
+            SearchOutcome::Aborted => {
+                state.finish(false);
+                return None;
+            }
+            SearchOutcome::Exhausted => {
+                block.timestamp = (Utc::now().timestamp() as u64).max(block.timestamp + 1);
+                println!("Nonce space exhausted for block #{}, rolling timestamp to {}", block.index, block.timestamp);
+            }
+        }
+    };
+    state.finish(true);
+
+    let duration = start.elapsed().as_secs_f64();
+    let thread_hashes = state.status().thread_hashes;
+    let total_hashes: u64 = thread_hashes.iter().sum();
     let stats = MiningStats {
-        block_index: index as u64,
-        timestamp: timestamp as u64,
+        block_index: block.index,
+        timestamp: block.timestamp,
         nonce,
         hash: hash.clone(),
         mining_time_sec: duration,
         difficulty,
-        estimated_hashrate: nonce as f64 / duration as f64,
+        estimated_hashrate: (duration > 0.0).then(|| total_hashes as f64 / duration),
+        total_hashes,
+        thread_hashes,
     };
 
-    let mut mining_stats = load_mining_stats();
-    mining_stats.push(stats);
-    save_mining_stats(&mining_stats).unwrap_or_else(|e| eprintln!("Failed to save mining stats: {}", e));
-
-    let mut balances = load_wallet_balances();
-    for tx in &transactions {
-        if tx.to == "miner_address" {
-            let entry = balances.entry("miner_address".to_string()).or_insert(HashMap::new());
-            *entry.entry(tx.network.clone()).or_insert(0.0) += tx.amount;
-        }
-    }
-    save_wallet_balances(&balances).unwrap_or_else(|e| eprintln!("Failed to save wallet balances: {}", e));
-
-    println!("Block mined: {} (nonce: {}, time: {}s)", hash, nonce, duration);
-    Block {
-        index: index as u64,
-        timestamp: timestamp as u64,
-        transactions,
-        previous_hash,
-        nonce,
-        hash,
-    }
-}
\ No newline at end of file
+    println!("Block mined: {} (nonce: {}, hashes: {}, time: {:.3}s)", hash, nonce, total_hashes, duration);
+    block.nonce = nonce;
+    block.hash = hash;
+    Some((block, stats))
+}
@@ -1,19 +1,57 @@
-use actix_web::{get, post, web, HttpResponse, Responder};
-use std::sync::{Arc, Mutex};
-use crate::Blockchain;
-use crate::Transaction;
-use serde::{Deserialize, Serialize};
+use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
+use std::sync::{Arc, Mutex, MutexGuard};
+use crate::error::{ApiError, ErrorCode};
+use crate::fees::TYPICAL_TRANSFER_BYTES;
+use crate::oracle::PriceFeed;
+use crate::signing::SigningStatus;
+use crate::status::TxStatus;
+use crate::history::{self, HistoryQuery};
+use crate::{encoding, explorer, lock_chain, now, Amount, Asset, Block, Blockchain, LockTime, MultisigPolicy, MultisigWitness, PartialSignature, Peg};
+use crate::{Transaction, TxError};
+use serde::Deserialize;
 
 #[derive(Deserialize)]
 pub struct TransactionRequest {
     pub from: String,
     pub to: String,
-    pub amount: f64,
-    pub peg_value: String,
+    // Semua jumlah dalam satuan terkecil aset (lamports, satoshi, wei), ditulis sebagai string
+    pub amount: Amount,
+    // Ditandatangani dalam bentuk kanonik, misal "15000 IDR" atau "1.5 USD" (lihat `Peg`)
+    pub peg_value: Peg,
     pub network: String,
-    // Hapus fee dari sini, ga perlu input manual
+    // Fee, timestamp, dan nonce ikut ditandatangani, jadi harus sama persis sama yang di-sign client.
+    // Fee minimal sesuai GET /fees/{network}
+    pub fee: Amount,
+    pub timestamp: u64,
+    // Nonce berikutnya dari `from`, ambil dari GET /nonce/{address}
+    pub nonce: u64,
+    // Base58 signature ed25519 dari `from` atas `encoding::transaction_payload`
     pub signature: Option<String>,
-    pub initial_balance: Option<f64>,
+    // Opsional: {"height": N} atau {"time": unix}, ikut ditandatangani
+    #[serde(default)]
+    pub lock_until: Option<LockTime>,
+    // Gantinya `signature` kalau `from` address multisig
+    #[serde(default)]
+    pub multisig: Option<MultisigWitness>,
+}
+
+// Endpoint /admin cuma aktif kalau env ini di-set, token-nya dikirim lewat header X-Admin-Token
+pub const ADMIN_TOKEN_ENV: &str = "ADMIN_TOKEN";
+pub const ADMIN_TOKEN_HEADER: &str = "X-Admin-Token";
+
+fn authorize_admin(req: &HttpRequest) -> Result<(), ApiError> {
+    let expected = match std::env::var(ADMIN_TOKEN_ENV) {
+        Ok(token) if !token.is_empty() => token,
+        _ => return Err(ApiError::new(ErrorCode::Forbidden, "Admin API is disabled")),
+    };
+    match req.headers().get(ADMIN_TOKEN_HEADER).and_then(|v| v.to_str().ok()) {
+        Some(token) if token == expected => Ok(()),
+        _ => Err(ApiError::new(ErrorCode::Unauthorized, "Invalid admin token")),
+    }
+}
+
+fn lock(data: &web::Data<Arc<Mutex<Blockchain>>>) -> MutexGuard<'_, Blockchain> {
+    lock_chain(data.get_ref())
 }
 
 #[derive(Deserialize)]
@@ -21,145 +59,387 @@ pub struct TransactionBatchRequest {
     transactions: Vec<TransactionRequest>,
 }
 
-#[derive(Serialize)]
-pub struct BlockResponse {
-    pub index: u64,
-    pub timestamp: u64,
-    pub previous_hash: String,
-    pub hash: String,
-    pub transactions: Vec<Transaction>,
+fn request_transaction(request: &TransactionRequest) -> Transaction {
+    let mut tx = Transaction {
+        from: request.from.clone(),
+        to: request.to.clone(),
+        amount: request.amount,
+        peg_value: request.peg_value.clone(),
+        network: request.network.clone(),
+        fee: request.fee,
+        signature: request.signature.clone(),
+        status: TxStatus::Pending,
+        txid: String::new(),
+        timestamp: request.timestamp,
+        nonce: request.nonce,
+        lock_until: request.lock_until,
+        multisig: request.multisig.clone(),
+    };
+    tx.txid = encoding::transaction_id(&tx);
+    tx
 }
 
-#[get("/block/{index}")]
-pub async fn get_block(path: web::Path<u64>, data: web::Data<Arc<Mutex<Blockchain>>>) -> impl Responder {
-    let blockchain = data.lock().unwrap_or_else(|e| panic!("Lock error: {:?}", e));
-    let block_index = path.into_inner();
-    if block_index < blockchain.chain.len() as u64 {
-        let block = &blockchain.chain[block_index as usize];
-        let response = BlockResponse {
-            index: block.index,
-            timestamp: block.timestamp,
-            previous_hash: block.previous_hash.clone(),
-            hash: block.hash.clone(),
-            transactions: block.transactions.clone(),
-        };
-        HttpResponse::Ok().json(response)
-    } else {
-        HttpResponse::NotFound().body("Block not found")
+// Masukin satu request ke mempool. Balikin txid transfernya.
+fn submit_request(blockchain: &mut Blockchain, request: &TransactionRequest) -> Result<String, TxError> {
+    let tx = request_transaction(request);
+    println!("Processing tx: from={}, to={}, amount={}, fee={}", tx.from, tx.to, tx.amount, tx.fee);
+    if let Err(e) = blockchain.submit_transaction(tx.clone()) {
+        println!("Validation failed for {}: {}", tx.from, e);
+        return Err(e);
     }
+    Ok(tx.txid)
+}
+
+#[get("/block/{index}")]
+pub async fn get_block(path: web::Path<u64>, data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    let block = explorer::block(&blockchain, path.into_inner()).ok_or_else(|| ApiError::not_found("Block not found"))?;
+    Ok(HttpResponse::Ok().json(block))
+}
+
+#[get("/block/hash/{hash}")]
+pub async fn get_block_by_hash(path: web::Path<String>, data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    let block = explorer::block_by_hash(&blockchain, &path.into_inner()).ok_or_else(|| ApiError::not_found("Block not found"))?;
+    Ok(HttpResponse::Ok().json(block))
+}
+
+#[get("/blocks/latest")]
+pub async fn get_latest_block(data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    let block = explorer::latest_block(&blockchain).ok_or_else(|| ApiError::not_found("Block not found"))?;
+    Ok(HttpResponse::Ok().json(block))
+}
+
+#[derive(Deserialize)]
+pub struct BlocksQuery {
+    #[serde(default)]
+    pub from: u64,
+    pub limit: Option<usize>,
+}
+
+// Ringkasan block tanpa isi transaksi, lanjut ke halaman berikutnya pakai `next`
+#[get("/blocks")]
+pub async fn get_blocks(query: web::Query<BlocksQuery>, data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    let limit = query.limit.unwrap_or(explorer::DEFAULT_PAGE_SIZE);
+    Ok(HttpResponse::Ok().json(explorer::blocks(&blockchain, query.from, limit)))
+}
+
+#[get("/chain/info")]
+pub async fn get_chain_info(data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    Ok(HttpResponse::Ok().json(explorer::chain_info(&blockchain)))
+}
+
+// Query bisa index block, hash block, txid, atau address
+#[get("/search/{query}")]
+pub async fn search(path: web::Path<String>, data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    let result = explorer::search(&blockchain, &path.into_inner()).ok_or_else(|| ApiError::not_found("Nothing matches the query"))?;
+    Ok(HttpResponse::Ok().json(result))
 }
 
 #[post("/transaction/single")]
 pub async fn add_single_transaction(
     transaction: web::Json<TransactionRequest>,
     data: web::Data<Arc<Mutex<Blockchain>>>,
-) -> impl Responder {
-    let mut blockchain = data.lock().unwrap_or_else(|e| panic!("Lock error: {:?}", e));
-    // Set fee otomatis 0.001
-    let fee = 0.001;
-    let mut tx = Transaction::new(
-        transaction.from.clone(),
-        transaction.to.clone(),
-        transaction.amount,
-        transaction.peg_value.clone(),
-        transaction.network.clone(),
-        fee, // Fee otomatis
-    );
-    tx.signature = transaction.signature.clone();
-    tx.status = "pending".to_string();
-    let initial_balance = transaction.initial_balance.unwrap_or(0.0);
-    if blockchain.wallet.get_balance(&tx.from, &tx.network) == 0.0 && tx.from != "genesis" && initial_balance > 0.0 {
-        blockchain.wallet.update_balance(&tx.from, &tx.network, initial_balance);
-        println!("Initialized balance for new address {}: {:.4} {}", tx.from, initial_balance, tx.network);
-    }
-
-    println!("Processing tx: from={}, to={}, amount={}, fee={}", tx.from, tx.to, tx.amount, tx.fee);
-    let sender_balance = blockchain.wallet.get_balance(&tx.from, &tx.network);
-    if tx.validate().is_ok() && sender_balance >= tx.amount + tx.fee {
-        let transactions = vec![tx.clone()];
-        blockchain.add_block(transactions);
-        if let Some(last_block) = blockchain.chain.last_mut() {
-            if let Some(last_tx) = last_block.transactions.last_mut() {
-                last_tx.status = "berhasil".to_string();
-            }
-        }
-        HttpResponse::Ok().body(format!("Transaction added with txid: {}", tx.txid))
-    } else {
-        tx.status = "gagal".to_string();
-        println!("Validation failed for {}: {:?}", tx.from, tx.validate().err().unwrap_or_else(|| "insufficient balance".into()));
-        HttpResponse::BadRequest().body("Invalid transaction or insufficient balance")
-    }
+) -> Result<HttpResponse, ApiError> {
+    let mut blockchain = lock(&data);
+    // Alasan penolakan juga bisa dicek belakangan lewat GET /transaction/{txid}
+    let txid = submit_request(&mut blockchain, &transaction).map_err(|e| {
+        let txid = request_transaction(&transaction).txid;
+        let reason = e.to_string();
+        ApiError::from(e).with_details(serde_json::json!({"txid": txid, "status": TxStatus::Rejected { reason }}))
+    })?;
+    Ok(HttpResponse::Accepted().json(serde_json::json!({"txid": txid, "status": TxStatus::Pending})))
 }
 
 #[post("/transaction")]
 pub async fn add_transaction(
     transaction: web::Json<TransactionBatchRequest>,
     data: web::Data<Arc<Mutex<Blockchain>>>,
-) -> impl Responder {
-    let mut blockchain = data.lock().unwrap_or_else(|e| panic!("Lock error: {:?}", e));
-    let mut valid_transactions = Vec::new();
-
+) -> Result<HttpResponse, ApiError> {
+    let mut blockchain = lock(&data);
+    let mut accepted = Vec::new();
+    let mut rejected = Vec::new();
     for tx_request in &transaction.transactions {
-        let fee = 0.001; // Fee otomatis buat batch
-        let mut tx = Transaction::new(
-            tx_request.from.clone(),
-            tx_request.to.clone(),
-            tx_request.amount,
-            tx_request.peg_value.clone(),
-            tx_request.network.clone(),
-            fee,
-        );
-        tx.signature = tx_request.signature.clone();
-        tx.status = "pending".to_string();
-        let initial_balance = tx_request.initial_balance.unwrap_or(0.0);
-        if blockchain.wallet.get_balance(&tx.from, &tx.network) == 0.0 && tx.from != "genesis" && initial_balance > 0.0 {
-            blockchain.wallet.update_balance(&tx.from, &tx.network, initial_balance);
-            println!("Initialized balance for new address {}: {:.4} {}", tx.from, initial_balance, tx.network);
-        }
-        {
-            println!("Processing tx: from={}, to={}, amount={}, fee={}", tx.from, tx.to, tx.amount, tx.fee);
-        }
-        let sender_balance = blockchain.wallet.get_balance(&tx.from, &tx.network);
-        if tx.validate().is_ok() && sender_balance >= tx.amount + tx.fee {
-            tx.status = "pending".to_string();
-            valid_transactions.push(tx.clone());
-        } else {
-            tx.status = "gagal".to_string();
-            println!("Validation failed for {}: {:?}", tx.from, tx.validate().err().unwrap_or_else(|| "insufficient balance".into()));
+        match submit_request(&mut blockchain, tx_request) {
+            Ok(txid) => accepted.push(txid),
+            Err(e) => rejected.push(serde_json::json!({
+                "txid": request_transaction(tx_request).txid,
+                "code": ErrorCode::from(&e),
+                "status": TxStatus::Rejected { reason: e.to_string() },
+            })),
         }
     }
 
-    if !valid_transactions.is_empty() {
-        blockchain.add_block(valid_transactions.clone());
-        HttpResponse::Ok().body(format!("{} transactions added successfully", valid_transactions.len()))
-    } else {
-        HttpResponse::BadRequest().body("No valid transactions to process")
+    if accepted.is_empty() {
+        return Err(ApiError::validation("No valid transactions to process").with_details(serde_json::json!({"rejected": rejected})));
+    }
+    Ok(HttpResponse::Accepted().json(serde_json::json!({"txids": accepted, "status": TxStatus::Pending, "rejected": rejected})))
+}
+
+// Mining sekarang juga, ga nunggu jadwal block producer
+#[post("/mine")]
+pub async fn mine_pending(data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    // Kalau miner background jalan, cukup dibangunin; mining-nya ga nahan lock chain
+    let miner = lock(&data).miner.clone();
+    if miner.is_enabled() {
+        miner.wake();
+        return Ok(HttpResponse::Accepted().json(serde_json::json!({"scheduled": true, "mining": miner.status()})));
     }
+    let blockchain = data.get_ref().clone();
+    let mined = web::block(move || {
+        let mut blockchain = lock_chain(&blockchain);
+        blockchain.produce_block()
+    })
+    .await
+    .map_err(|e| ApiError::internal(format!("Mining failed: {}", e)))?;
+    Ok(match mined {
+        Some(height) => HttpResponse::Ok().json(serde_json::json!({"mined": true, "height": height})),
+        None => HttpResponse::Ok().json(serde_json::json!({"mined": false, "reason": "mempool is empty"})),
+    })
+}
+
+#[get("/mining/status")]
+pub async fn get_mining_status(data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let miner = lock(&data).miner.clone();
+    Ok(HttpResponse::Ok().json(miner.status()))
+}
+
+#[derive(Deserialize)]
+pub struct FeeQuery {
+    // Ukuran transaksi dalam byte (lihat `fees::transaction_size`), default ukuran transfer biasa
+    pub size: Option<usize>,
+}
+
+// Estimasi fee dari kebijakan fee chainspec plus kondisi mempool sekarang
+#[get("/fees/{network}")]
+pub async fn get_fee_estimate(
+    path: web::Path<String>,
+    query: web::Query<FeeQuery>,
+    data: web::Data<Arc<Mutex<Blockchain>>>,
+) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    let network = path.into_inner();
+    let size = query.size.unwrap_or(TYPICAL_TRANSFER_BYTES);
+    let estimate = blockchain.spec.fees.estimate(&blockchain.spec.assets, &network, size, blockchain.mempool.len()).map_err(ApiError::not_found)?;
+    Ok(HttpResponse::Ok().json(estimate))
+}
+
+#[get("/assets")]
+pub async fn get_assets(data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    Ok(HttpResponse::Ok().json(blockchain.spec.assets.assets()))
+}
+
+#[get("/assets/{symbol}")]
+pub async fn get_asset(path: web::Path<String>, data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    let symbol = path.into_inner();
+    let asset = blockchain.spec.assets.get(&symbol).ok_or_else(|| ApiError::not_found(format!("Unknown asset {}", symbol)))?;
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "asset": asset,
+        "total_supply": blockchain.wallet.total_supply(&symbol),
+    })))
+}
+
+// Tambah atau ganti aset. Ditolak kalau bikin chain yang udah ada jadi ga valid.
+#[post("/admin/assets")]
+pub async fn upsert_asset(req: HttpRequest, asset: web::Json<Asset>, data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    authorize_admin(&req)?;
+    let mut blockchain = lock(&data);
+    let asset = asset.into_inner();
+    let created = blockchain.upsert_asset(asset.clone()).map_err(ApiError::validation)?;
+    println!("Asset {} {}", asset.symbol, if created { "registered" } else { "updated" });
+    Ok(HttpResponse::Ok().json(serde_json::json!({"asset": asset, "created": created})))
+}
+
+#[get("/oracle/prices")]
+pub async fn get_prices(data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    let oracle = &blockchain.oracle;
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "feed": oracle.feed(),
+        "fresh": oracle.is_fresh(now()),
+        "tolerance_bps": oracle.tolerance_bps,
+    })))
+}
+
+// Stub feed HTTP: harga di-push langsung, format sama dengan file feed
+#[post("/admin/oracle/prices")]
+pub async fn push_prices(req: HttpRequest, feed: web::Json<PriceFeed>, data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    authorize_admin(&req)?;
+    let mut blockchain = lock(&data);
+    let count = blockchain.oracle.ingest(feed.into_inner(), now()).map_err(ApiError::validation)?;
+    println!("Oracle: {} prices pushed via API", count);
+    Ok(HttpResponse::Ok().json(blockchain.oracle.feed()))
+}
+
+#[delete("/admin/assets/{symbol}")]
+pub async fn remove_asset(req: HttpRequest, path: web::Path<String>, data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    authorize_admin(&req)?;
+    let mut blockchain = lock(&data);
+    let asset = blockchain.remove_asset(&path.into_inner()).map_err(ApiError::validation)?;
+    Ok(HttpResponse::Ok().json(serde_json::json!({"removed": asset})))
+}
+
+#[derive(Deserialize)]
+pub struct MultisigAddressRequest {
+    pub threshold: u8,
+    // Address ed25519 tiap signer
+    pub signers: Vec<String>,
+}
+
+#[post("/multisig/address")]
+pub async fn create_multisig_address(request: web::Json<MultisigAddressRequest>) -> Result<HttpResponse, ApiError> {
+    let request = request.into_inner();
+    let policy = MultisigPolicy::new(request.threshold, request.signers).map_err(ApiError::validation)?;
+    Ok(HttpResponse::Ok().json(serde_json::json!({"address": policy.address(), "policy": policy})))
+}
+
+// Transaksi dari address multisig (field `multisig` wajib diisi policy-nya) mulai ngumpulin tanda tangan.
+// Tiap signer tanda tangan `encoding::transaction_payload` lalu kirim ke /multisig/transactions/{txid}/signatures.
+#[post("/multisig/transactions")]
+pub async fn propose_multisig(request: web::Json<TransactionRequest>, data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let mut blockchain = lock(&data);
+    let status = blockchain.propose_multisig(request_transaction(&request))?;
+    Ok(HttpResponse::Ok().json(status))
+}
+
+#[get("/multisig/transactions/{txid}")]
+pub async fn get_multisig(path: web::Path<String>, data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    let tx = blockchain.signing.get(&path.into_inner()).ok_or_else(|| ApiError::not_found("Multisig transaction not found"))?;
+    Ok(HttpResponse::Ok().json(SigningStatus::of(tx, false)))
+}
+
+// Begitu threshold tercapai transaksinya langsung masuk mempool (`submitted: true`)
+#[post("/multisig/transactions/{txid}/signatures")]
+pub async fn sign_multisig(
+    path: web::Path<String>,
+    signature: web::Json<PartialSignature>,
+    data: web::Data<Arc<Mutex<Blockchain>>>,
+) -> Result<HttpResponse, ApiError> {
+    let mut blockchain = lock(&data);
+    let txid = path.into_inner();
+    if blockchain.signing.get(&txid).is_none() {
+        return Err(ApiError::not_found("Multisig transaction not found"));
+    }
+    let status = blockchain.sign_multisig(&txid, signature.into_inner())?;
+    Ok(HttpResponse::Ok().json(status))
+}
+
+#[derive(Deserialize)]
+pub struct FaucetRequest {
+    pub address: String,
+}
+
+#[get("/faucet")]
+pub async fn get_faucet(data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    let faucet = blockchain.faucet.as_ref().ok_or_else(|| ApiError::not_found("Faucet is disabled"))?;
+    let balance = blockchain.mempool.available_balance(&blockchain.wallet, faucet.address(), faucet.network());
+    Ok(HttpResponse::Ok().json(serde_json::json!({"faucet": faucet.info(&blockchain.spec), "balance": balance})))
+}
+
+// Dana faucet dikirim sebagai transfer biasa, IP diambil dari koneksi buat rate limit
+#[post("/faucet")]
+pub async fn request_faucet(
+    req: HttpRequest,
+    request: web::Json<FaucetRequest>,
+    data: web::Data<Arc<Mutex<Blockchain>>>,
+) -> Result<HttpResponse, ApiError> {
+    let mut blockchain = lock(&data);
+    let ip = req.peer_addr().map(|addr| addr.ip());
+    if blockchain.faucet.is_none() {
+        return Err(ApiError::not_found("Faucet is disabled"));
+    }
+    let tx = blockchain.request_faucet(&request.address, ip)?;
+    Ok(HttpResponse::Accepted().json(serde_json::json!({"txid": tx.txid, "status": TxStatus::Pending, "amount": tx.amount, "network": tx.network})))
+}
+
+#[get("/mempool")]
+pub async fn get_mempool(data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    Ok(HttpResponse::Ok().json(blockchain.mempool.transactions()))
 }
 
 #[get("/wallet/{address}/{network}")]
-pub async fn get_wallet(path: web::Path<(String, String)>, data: web::Data<Arc<Mutex<Blockchain>>>) -> impl Responder {
-    let blockchain = data.lock().unwrap_or_else(|e| panic!("Lock error: {:?}", e));
+pub async fn get_wallet(path: web::Path<(String, String)>, data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
     let (address, network) = path.into_inner();
     let balance = blockchain.wallet.get_balance(&address, &network);
-    HttpResponse::Ok().json(serde_json::json!({"address": address, "network": network, "balance": balance}))
+    let asset = blockchain.spec.assets.get(&network);
+    // Nilai saldo di tiap mata uang yang ada harganya di oracle
+    let fiat = asset.map(|a| blockchain.oracle.valuations(balance, a.decimals, &a.symbol, now())).unwrap_or_default();
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "address": address,
+        "balance": balance,
+        "display_balance": blockchain.spec.assets.display_amount(balance, &network),
+        "decimals": asset.map(|a| a.decimals),
+        "fiat": fiat,
+        "network": network,
+    })))
 }
 
-#[get("/history/{address}")]
-pub async fn get_history(path: web::Path<String>, data: web::Data<Arc<Mutex<Blockchain>>>) -> impl Responder {
-    let blockchain = data.lock().unwrap_or_else(|e| panic!("Lock error: {:?}", e));
+// `nonce` = jumlah transaksi `address` yang udah masuk chain, `next_nonce` = nonce buat transaksi berikutnya
+// (udah ngitung transaksi yang masih pending di mempool)
+#[get("/nonce/{address}")]
+pub async fn get_nonce(path: web::Path<String>, data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
     let address = path.into_inner();
-    let history = blockchain.history.iter().filter(|tx| tx.from == address || tx.to == address).cloned().collect::<Vec<_>>();
-    HttpResponse::Ok().json(history)
+    let nonce = blockchain.wallet.get_nonce(&address);
+    let next_nonce = blockchain.mempool.next_nonce(&blockchain.wallet, &address);
+    Ok(HttpResponse::Ok().json(serde_json::json!({"address": address, "nonce": nonce, "next_nonce": next_nonce})))
+}
+
+// Query: direction=sent|received, network, status=pending|included|confirmed, from_time, to_time,
+// from_height, to_height, order=asc|desc (default desc), limit, cursor (dari `next_cursor`)
+#[get("/history/{address}")]
+pub async fn get_history(path: web::Path<String>, query: web::Query<HistoryQuery>, data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    let page = history::address_history(&blockchain, &path.into_inner(), &query).map_err(ApiError::validation)?;
+    Ok(HttpResponse::Ok().json(page))
 }
 
 #[get("/transaction/{txid}")]
-pub async fn get_transaction(path: web::Path<String>, data: web::Data<Arc<Mutex<Blockchain>>>) -> impl Responder {
-    let blockchain = data.lock().unwrap_or_else(|e| panic!("Lock error: {:?}", e));
+pub async fn get_transaction(path: web::Path<String>, data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
     let txid = path.into_inner();
-    let tx = blockchain.history.iter().find(|t| t.txid == txid);
-    match tx {
-        Some(transaction) => HttpResponse::Ok().json(transaction),
-        None => HttpResponse::NotFound().body("Transaction not found"),
-    }
-}
\ No newline at end of file
+    let transaction = blockchain.find_transaction(&txid).ok_or_else(|| ApiError::not_found("Transaction not found"))?;
+    Ok(HttpResponse::Ok().json(transaction))
+}
+
+#[get("/transaction/{txid}/proof")]
+pub async fn get_transaction_proof(path: web::Path<String>, data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    let txid = path.into_inner();
+    let (header, proof) = blockchain.transaction_proof(&txid).ok_or_else(|| ApiError::not_found("Transaction not found"))?;
+    Ok(HttpResponse::Ok().json(serde_json::json!({"header": header, "proof": proof})))
+}
+
+// Block dari peer, bisa nyambung ke tip, masuk cabang samping, atau bikin reorg
+#[post("/block")]
+pub async fn submit_block(block: web::Json<Block>, data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let mut blockchain = lock(&data);
+    let outcome = blockchain.accept_block(block.into_inner()).map_err(ApiError::validation)?;
+    Ok(HttpResponse::Ok().json(outcome))
+}
+
+#[get("/chain/tips")]
+pub async fn get_chain_tips(data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    Ok(HttpResponse::Ok().json(blockchain.tips()))
+}
+
+#[get("/chain/validate")]
+pub async fn validate_chain(data: web::Data<Arc<Mutex<Blockchain>>>) -> Result<HttpResponse, ApiError> {
+    let blockchain = lock(&data);
+    // Chain yang ga valid tetap 200, hasil validasinya ada di body
+    Ok(match blockchain.validate_chain() {
+        Ok(()) => HttpResponse::Ok().json(serde_json::json!({"valid": true, "height": blockchain.chain.len()})),
+        Err(e) => HttpResponse::Ok().json(serde_json::json!({"valid": false, "height": blockchain.chain.len(), "error": e, "message": e.to_string()})),
+    })
+}
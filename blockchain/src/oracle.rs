@@ -0,0 +1,234 @@
+use crate::ledger::COINBASE_ADDRESS;
+use crate::peg::PEG_DECIMALS;
+use crate::{lock_chain, now, Amount, AssetRegistry, Blockchain, Transaction};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+// Harga aset dalam fiat, buat ngecek `peg_value` dan nampilin saldo dalam fiat.
+// Ini kebijakan node, bukan konsensus: harga beda-beda tiap node dan berubah terus,
+// jadi cuma dicek waktu transaksi masuk mempool, ga pernah waktu validasi block.
+
+// Presisi harga dan nilai fiat
+pub const PRICE_DECIMALS: u8 = 8;
+pub const PRICE_FEED_ENV: &str = "PRICE_FEED";
+pub const DEFAULT_PRICE_FEED: &str = "prices.json";
+pub const PEG_TOLERANCE_ENV: &str = "PEG_TOLERANCE_BPS";
+// 500 basis point = 5%
+pub const DEFAULT_TOLERANCE_BPS: u64 = 500;
+pub const DEFAULT_MAX_AGE_SECS: u64 = 3600;
+
+// Jumlah aset diturunin ke 9 desimal dulu sebelum dikali harga, biar ga overflow u128
+const VALUE_DECIMALS: u8 = 9;
+
+// Format file feed, sama dengan body POST /admin/oracle/prices:
+// {"updated_at": 1700000000, "prices": {"SOL": {"USD": "150.25", "IDR": "2400000"}}}
+// Harga = nilai 1 unit utuh aset, ditulis desimal.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PriceFeed {
+    #[serde(default)]
+    pub updated_at: u64,
+    pub prices: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl PriceFeed {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let data = std::fs::read_to_string(path).map_err(|e| format!("Failed to read price feed {}: {}", path.display(), e))?;
+        serde_json::from_str(&data).map_err(|e| format!("Invalid price feed {}: {}", path.display(), e))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OracleConfig {
+    pub feed_path: PathBuf,
+    pub tolerance_bps: u64,
+    // Harga yang lebih tua dari ini dianggap ga ada
+    pub max_age_secs: u64,
+    // Seberapa sering file feed dicek perubahannya
+    pub poll_interval: Duration,
+}
+
+impl Default for OracleConfig {
+    fn default() -> Self {
+        OracleConfig {
+            feed_path: PathBuf::from(DEFAULT_PRICE_FEED),
+            tolerance_bps: DEFAULT_TOLERANCE_BPS,
+            max_age_secs: DEFAULT_MAX_AGE_SECS,
+            poll_interval: Duration::from_secs(10),
+        }
+    }
+}
+
+impl OracleConfig {
+    // Lokasi feed lewat env PRICE_FEED, toleransi lewat PEG_TOLERANCE_BPS
+    pub fn from_env() -> Self {
+        let mut config = OracleConfig::default();
+        if let Ok(path) = std::env::var(PRICE_FEED_ENV) {
+            config.feed_path = PathBuf::from(path);
+        }
+        match std::env::var(PEG_TOLERANCE_ENV).map(|v| v.parse::<u64>()) {
+            Ok(Ok(bps)) => config.tolerance_bps = bps,
+            Ok(Err(_)) => eprintln!("Invalid {}, using {} bps", PEG_TOLERANCE_ENV, DEFAULT_TOLERANCE_BPS),
+            Err(_) => {}
+        }
+        config
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PriceOracle {
+    // aset -> mata uang -> harga 1 unit utuh aset (PRICE_DECIMALS)
+    prices: BTreeMap<String, BTreeMap<String, Amount>>,
+    pub updated_at: u64,
+    pub tolerance_bps: u64,
+    pub max_age_secs: u64,
+}
+
+impl Default for PriceOracle {
+    fn default() -> Self {
+        PriceOracle {
+            prices: BTreeMap::new(),
+            updated_at: 0,
+            tolerance_bps: DEFAULT_TOLERANCE_BPS,
+            max_age_secs: DEFAULT_MAX_AGE_SECS,
+        }
+    }
+}
+
+impl PriceOracle {
+    // Ganti semua harga dengan isi feed. Feed yang ada harga rusak ditolak utuh.
+    // Feed tanpa `updated_at` dianggap harga per `now`.
+    pub fn ingest(&mut self, feed: PriceFeed, now: u64) -> Result<usize, String> {
+        let mut prices: BTreeMap<String, BTreeMap<String, Amount>> = BTreeMap::new();
+        for (asset, quotes) in feed.prices {
+            let asset = asset.to_uppercase();
+            for (currency, price) in quotes {
+                let parsed = Amount::parse_decimal(&price, PRICE_DECIMALS)
+                    .map_err(|e| format!("Invalid price {} {}/{}: {}", price, asset, currency, e))?;
+                prices.entry(asset.clone()).or_default().insert(currency.to_uppercase(), parsed);
+            }
+        }
+        let count = prices.values().map(BTreeMap::len).sum();
+        self.prices = prices;
+        self.updated_at = if feed.updated_at == 0 { now } else { feed.updated_at };
+        Ok(count)
+    }
+
+    pub fn is_fresh(&self, now: u64) -> bool {
+        self.updated_at.saturating_add(self.max_age_secs) >= now
+    }
+
+    // Harga 1 unit utuh `asset` dalam `currency`. Aset ke simbolnya sendiri selalu 1.
+    pub fn price(&self, asset: &str, currency: &str, now: u64) -> Option<Amount> {
+        if asset == currency {
+            return Some(Amount::from_units(10u128.pow(PRICE_DECIMALS as u32)));
+        }
+        if !self.is_fresh(now) {
+            return None;
+        }
+        self.prices.get(asset)?.get(currency).copied()
+    }
+
+    // Nilai `amount` (satuan terkecil, `decimals` desimal) dalam `currency`, presisi PRICE_DECIMALS.
+    // None kalau ga ada harga.
+    pub fn value(&self, amount: Amount, decimals: u8, asset: &str, currency: &str, now: u64) -> Result<Option<Amount>, String> {
+        let Some(price) = self.price(asset, currency, now) else {
+            return Ok(None);
+        };
+        let scaled = if decimals >= VALUE_DECIMALS {
+            Amount::from_units(amount.units() / 10u128.pow((decimals - VALUE_DECIMALS) as u32))
+        } else {
+            amount.checked_mul(10u128.pow((VALUE_DECIMALS - decimals) as u32))?
+        };
+        let value = scaled.checked_mul(price.units()).map_err(|_| format!("Value of {} {} in {} is too large", amount, asset, currency))?;
+        Ok(Some(value.div_floor(10u128.pow(VALUE_DECIMALS as u32))))
+    }
+
+    // Nilai `amount` di semua mata uang yang ada harganya, buat endpoint wallet
+    pub fn valuations(&self, amount: Amount, decimals: u8, asset: &str, now: u64) -> BTreeMap<String, String> {
+        let Some(quotes) = self.prices.get(asset) else {
+            return BTreeMap::new();
+        };
+        quotes
+            .keys()
+            .filter_map(|currency| {
+                let value = self.value(amount, decimals, asset, currency, now).ok()??;
+                Some((currency.clone(), value.to_decimal_string(PRICE_DECIMALS)))
+            })
+            .collect()
+    }
+
+    // Peg yang dideklarasiin harus dalam toleransi dari harga oracle.
+    // Kalau oracle ga punya harga (atau harganya basi) peg ga bisa dicek, jadi dibiarin lolos.
+    pub fn verify_peg(&self, tx: &Transaction, assets: &AssetRegistry, now: u64) -> Result<(), String> {
+        if tx.from == COINBASE_ADDRESS {
+            return Ok(());
+        }
+        // Network ga dikenal ditolak ledger, bukan urusan oracle
+        let Some(asset) = assets.get(&tx.network) else {
+            return Ok(());
+        };
+        let peg = &tx.peg_value;
+        let Some(expected) = self.value(tx.amount, asset.decimals, &asset.symbol, &peg.currency, now)? else {
+            return Ok(());
+        };
+        let declared = peg.amount.div_floor(10u128.pow((PEG_DECIMALS - PRICE_DECIMALS) as u32));
+        let allowed = expected.units().saturating_mul(self.tolerance_bps as u128) / 10_000;
+        if declared.units().abs_diff(expected.units()) > allowed {
+            return Err(format!(
+                "Peg value {} is more than {}% away from the oracle price: {} {} is worth {} {}",
+                peg,
+                Amount::from_units(self.tolerance_bps as u128).to_decimal_string(2),
+                asset.display_amount(tx.amount),
+                asset.symbol,
+                expected.to_decimal_string(PRICE_DECIMALS),
+                peg.currency
+            ));
+        }
+        Ok(())
+    }
+
+    // Kebalikan `ingest`, buat GET /oracle/prices
+    pub fn feed(&self) -> PriceFeed {
+        let prices = self
+            .prices
+            .iter()
+            .map(|(asset, quotes)| {
+                let quotes = quotes.iter().map(|(c, p)| (c.clone(), p.to_decimal_string(PRICE_DECIMALS))).collect();
+                (asset.clone(), quotes)
+            })
+            .collect();
+        PriceFeed { updated_at: self.updated_at, prices }
+    }
+}
+
+// Baca ulang file feed tiap kali berubah. Harga yang di-push lewat API tetap dipakai
+// sampai file-nya berubah lagi.
+pub fn spawn_price_feed(blockchain: Arc<Mutex<Blockchain>>, config: OracleConfig) -> thread::JoinHandle<()> {
+    {
+        let mut blockchain = lock_chain(&blockchain);
+        blockchain.oracle.tolerance_bps = config.tolerance_bps;
+        blockchain.oracle.max_age_secs = config.max_age_secs;
+    }
+    if !config.feed_path.exists() {
+        println!("No price feed at {}, peg values are not checked until prices are pushed", config.feed_path.display());
+    }
+    thread::spawn(move || {
+        let mut last_modified: Option<SystemTime> = None;
+        loop {
+            let modified = std::fs::metadata(&config.feed_path).and_then(|m| m.modified()).ok();
+            if modified.is_some() && modified != last_modified {
+                last_modified = modified;
+                match PriceFeed::load(&config.feed_path).and_then(|feed| lock_chain(&blockchain).oracle.ingest(feed, now())) {
+                    Ok(count) => println!("Oracle: loaded {} prices from {}", count, config.feed_path.display()),
+                    Err(e) => eprintln!("Oracle: {}", e),
+                }
+            }
+            thread::sleep(config.poll_interval);
+        }
+    })
+}
@@ -0,0 +1,96 @@
+use crate::{encoding, MultisigWitness, PartialSignature, Transaction, TxError};
+use serde::Serialize;
+use std::collections::HashMap;
+
+// Transaksi multisig yang lagi ngumpulin tanda tangan. Belum masuk mempool dan ga di-broadcast
+// sampai tanda tangannya cukup.
+#[derive(Debug, Clone, Default)]
+pub struct SigningPool {
+    proposals: HashMap<String, Transaction>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SigningStatus {
+    pub txid: String,
+    pub address: String,
+    pub threshold: u8,
+    pub signed: Vec<String>,
+    pub missing: Vec<String>,
+    pub complete: bool,
+    // True kalau udah diterima mempool (dan dihapus dari pool ini)
+    pub submitted: bool,
+    pub transaction: Transaction,
+}
+
+impl SigningStatus {
+    pub fn of(tx: &Transaction, submitted: bool) -> Self {
+        let (threshold, signed, missing, complete) = match &tx.multisig {
+            Some(w) => (w.policy.threshold, w.signatures.iter().map(|s| s.signer.clone()).collect(), w.missing_signers(), w.is_complete()),
+            None => (0, vec![], vec![], false),
+        };
+        SigningStatus {
+            txid: tx.txid.clone(),
+            address: tx.from.clone(),
+            threshold,
+            signed,
+            missing,
+            complete,
+            submitted,
+            transaction: tx.clone(),
+        }
+    }
+}
+
+impl SigningPool {
+    pub fn len(&self) -> usize {
+        self.proposals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.proposals.is_empty()
+    }
+
+    pub fn get(&self, txid: &str) -> Option<&Transaction> {
+        self.proposals.get(txid)
+    }
+
+    // Policy harus cocok sama `from`, tanda tangan yang udah ikut dicek satu-satu
+    pub fn propose(&mut self, mut tx: Transaction) -> Result<&Transaction, TxError> {
+        let witness = tx.multisig.take().ok_or_else(|| TxError::Invalid(format!("Transaction {} has no multisig policy", tx.txid)))?;
+        witness.policy.validate().map_err(TxError::Invalid)?;
+        if witness.policy.address() != tx.from {
+            return Err(TxError::Invalid(format!("Multisig policy does not match address {}", tx.from)));
+        }
+        if tx.signature.is_some() {
+            return Err(TxError::InvalidSignature(format!("Multisig transaction {} must not carry a single signature", tx.txid)));
+        }
+        tx.txid = encoding::transaction_id(&tx);
+        if self.proposals.contains_key(&tx.txid) {
+            return Err(TxError::Conflict(format!("Transaction {} is already collecting signatures", tx.txid)));
+        }
+        let payload = encoding::transaction_payload(&tx);
+        let mut collected = MultisigWitness::new(witness.policy);
+        for sig in witness.signatures {
+            collected.add_signature(&payload, sig).map_err(TxError::InvalidSignature)?;
+        }
+        tx.multisig = Some(collected);
+        let txid = tx.txid.clone();
+        Ok(self.proposals.entry(txid).or_insert(tx))
+    }
+
+    pub fn sign(&mut self, txid: &str, signature: PartialSignature) -> Result<&Transaction, TxError> {
+        let tx = self
+            .proposals
+            .get_mut(txid)
+            .ok_or_else(|| TxError::Invalid(format!("No multisig transaction {} is collecting signatures", txid)))?;
+        let payload = encoding::transaction_payload(tx);
+        if let Some(witness) = tx.multisig.as_mut() {
+            witness.add_signature(&payload, signature).map_err(TxError::InvalidSignature)?;
+        }
+        Ok(tx)
+    }
+
+    pub fn remove(&mut self, txid: &str) -> Option<Transaction> {
+        self.proposals.remove(txid)
+    }
+}
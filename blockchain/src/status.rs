@@ -0,0 +1,88 @@
+use crate::Transaction;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+// Berapa block (termasuk block yang memuat transaksinya) sampai transaksi dianggap confirmed
+pub const CONFIRMATION_DEPTH: u64 = 6;
+pub const DEFAULT_LOG_CAPACITY: usize = 10_000;
+
+// Di JSON: {"state": "included", "block": 3}, {"state": "rejected", "reason": "..."}, dst.
+// Ga ikut encoding, jadi nilai di dalam block cuma catatan lokal; status sebenarnya dihitung
+// ulang lewat `Blockchain::transaction_status`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum TxStatus {
+    // Di mempool, atau multisig yang masih ngumpulin tanda tangan
+    #[default]
+    Pending,
+    Included { block: u64 },
+    Confirmed { depth: u64 },
+    Rejected { reason: String },
+    Expired,
+}
+
+impl TxStatus {
+    // Status transaksi di block `block` kalau tip chain ada di height `tip`
+    pub fn in_chain(block: u64, tip: u64) -> Self {
+        let depth = tip.saturating_sub(block) + 1;
+        if depth >= CONFIRMATION_DEPTH {
+            TxStatus::Confirmed { depth }
+        } else {
+            TxStatus::Included { block }
+        }
+    }
+}
+
+// Transaksi yang ditolak atau kadaluarsa, biar client bisa nanya kenapa transfernya gagal.
+// Kalau penuh yang paling lama dibuang.
+#[derive(Debug, Clone)]
+pub struct TxLog {
+    entries: HashMap<String, Transaction>,
+    order: VecDeque<String>,
+    capacity: usize,
+}
+
+impl Default for TxLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOG_CAPACITY)
+    }
+}
+
+impl TxLog {
+    pub fn new(capacity: usize) -> Self {
+        TxLog { entries: HashMap::new(), order: VecDeque::new(), capacity }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn record(&mut self, mut tx: Transaction, status: TxStatus) {
+        tx.status = status;
+        if !self.entries.contains_key(&tx.txid) {
+            self.order.push_back(tx.txid.clone());
+        }
+        self.entries.insert(tx.txid.clone(), tx);
+        while self.entries.len() > self.capacity {
+            match self.order.pop_front() {
+                Some(oldest) => self.entries.remove(&oldest),
+                None => break,
+            };
+        }
+    }
+
+    pub fn get(&self, txid: &str) -> Option<&Transaction> {
+        self.entries.get(txid)
+    }
+
+    // Dipanggil kalau transaksi yang pernah ditolak akhirnya diterima
+    pub fn remove(&mut self, txid: &str) -> Option<Transaction> {
+        let tx = self.entries.remove(txid)?;
+        self.order.retain(|id| id != txid);
+        Some(tx)
+    }
+}
@@ -0,0 +1,131 @@
+use sha2::{Digest, Sha256};
+use crate::index::ChainIndex;
+use crate::Block;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+pub const BLOCKS_FILE: &str = "blocks.jsonl";
+pub const INDEX_FILE: &str = "index.json";
+
+// Block store append-only, satu baris per block: `<sha256 dari json>\t<json>`
+#[derive(Debug, Clone)]
+pub struct BlockStore {
+    path: PathBuf,
+    index_path: PathBuf,
+}
+
+impl BlockStore {
+    pub fn open(data_dir: impl AsRef<Path>) -> Result<Self, std::io::Error> {
+        fs::create_dir_all(data_dir.as_ref())?;
+        Ok(BlockStore { path: data_dir.as_ref().join(BLOCKS_FILE), index_path: data_dir.as_ref().join(INDEX_FILE) })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // Block dari semua cabang ditulis berurutan, parent selalu muncul duluan.
+    // Record terakhir yang rusak (write kepotong pas crash) dibuang. Kalau yang rusak ada di tengah
+    // (checksum salah, parent ga dikenal, index loncat), balikin InvalidData dan file ga disentuh.
+    pub fn load(&self) -> Result<Vec<Block>, std::io::Error> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut reader = BufReader::new(file);
+        let mut blocks: Vec<Block> = Vec::new();
+        let mut known: HashMap<String, u64> = HashMap::new();
+        let mut valid_len: u64 = 0;
+        // Dibaca per byte, bukan `read_line`: record yang kepotong di tengah karakter UTF-8 juga dianggap tail sobek
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line)?;
+            if read == 0 {
+                break;
+            }
+            match decode_record(&line, &known) {
+                Ok(block) => {
+                    known.insert(block.hash.clone(), block.index);
+                    blocks.push(block);
+                    valid_len += read as u64;
+                }
+                Err(reason) => {
+                    if !reader.fill_buf()?.is_empty() {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            format!("Block store {}: corrupted record after block #{} ({})", self.path.display(), blocks.len(), reason),
+                        ));
+                    }
+                    eprintln!(
+                        "Block store {}: discarding corrupted tail after block #{} ({})",
+                        self.path.display(),
+                        blocks.len(),
+                        reason
+                    );
+                    OpenOptions::new().write(true).open(&self.path)?.set_len(valid_len)?;
+                    break;
+                }
+            }
+        }
+        Ok(blocks)
+    }
+
+    pub fn append(&self, block: &Block) -> Result<(), std::io::Error> {
+        let json = serde_json::to_string(block)?;
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.write_all(format!("{}\t{}\n", checksum(&json), json).as_bytes())?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    // Snapshot index terakhir. None kalau belum ada atau rusak, nanti dibangun ulang dari block.
+    pub fn load_index(&self) -> Option<ChainIndex> {
+        let data = fs::read_to_string(&self.index_path).ok()?;
+        match serde_json::from_str(&data) {
+            Ok(index) => Some(index),
+            Err(e) => {
+                eprintln!("Ignoring invalid index snapshot {}: {}", self.index_path.display(), e);
+                None
+            }
+        }
+    }
+
+    // Ditulis ke file sementara dulu lalu di-rename, biar snapshot ga pernah setengah jadi
+    pub fn save_index(&self, index: &ChainIndex) -> Result<(), std::io::Error> {
+        let tmp = self.index_path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string(index)?)?;
+        fs::rename(&tmp, &self.index_path)
+    }
+}
+
+fn checksum(json: &str) -> String {
+    format!("{:x}", Sha256::digest(json.as_bytes()))
+}
+
+fn decode_record(line: &[u8], known: &HashMap<String, u64>) -> Result<Block, String> {
+    let record = line.strip_suffix(b"\n").ok_or("incomplete record")?;
+    let record = std::str::from_utf8(record).map_err(|_| "record is not valid UTF-8")?;
+    let (sum, json) = record.split_once('\t').ok_or("malformed record")?;
+    if checksum(json) != sum {
+        return Err("checksum mismatch".to_string());
+    }
+    let block: Block = serde_json::from_str(json).map_err(|e| format!("invalid block: {}", e))?;
+
+    let expected_index = if known.is_empty() {
+        0
+    } else {
+        known
+            .get(&block.previous_hash)
+            .map(|parent_index| parent_index + 1)
+            .ok_or_else(|| format!("block #{} has unknown parent {}", block.index, block.previous_hash))?
+    };
+    if block.index != expected_index {
+        return Err(format!("expected index {}, found {}", expected_index, block.index));
+    }
+    Ok(block)
+}
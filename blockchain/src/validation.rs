@@ -0,0 +1,138 @@
+use crate::chainspec::ChainSpec;
+use crate::difficulty::{meets_target, next_difficulty};
+use crate::{encoding, ledger, merkle, Block, LockTime, Wallet};
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ValidationErrorKind {
+    IndexMismatch { expected: u64, found: u64 },
+    BrokenLink { expected: String, found: String },
+    HashMismatch { computed: String, stored: String },
+    MerkleMismatch { computed: String, stored: String },
+    InsufficientWork { difficulty: u64 },
+    UnexpectedDifficulty { expected: u64, found: u64 },
+    TimestampRegression { previous: u64, found: u64 },
+    TimestampTooFarAhead { limit: u64, found: u64 },
+    InvalidTransaction { txid: String, reason: String },
+    InvalidCoinbase { reason: String },
+    ImmatureTransaction { txid: String, lock_until: LockTime },
+    GenesisMismatch { expected: String, found: String },
+}
+
+// Error validasi selalu nunjuk ke block pertama yang bermasalah
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChainValidationError {
+    pub block_index: u64,
+    pub block_hash: String,
+    pub kind: ValidationErrorKind,
+}
+
+impl fmt::Display for ChainValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "block #{} ({}): ", self.block_index, self.block_hash)?;
+        match &self.kind {
+            ValidationErrorKind::IndexMismatch { expected, found } => write!(f, "expected index {}, found {}", expected, found),
+            ValidationErrorKind::BrokenLink { expected, found } => write!(f, "previous_hash {} does not match {}", found, expected),
+            ValidationErrorKind::HashMismatch { computed, stored } => write!(f, "stored hash {} but computed {}", stored, computed),
+            ValidationErrorKind::MerkleMismatch { computed, stored } => write!(f, "merkle_root {} does not match transactions ({})", stored, computed),
+            ValidationErrorKind::InsufficientWork { difficulty } => write!(f, "hash does not meet difficulty {}", difficulty),
+            ValidationErrorKind::UnexpectedDifficulty { expected, found } => write!(f, "difficulty {} but retarget expects {}", found, expected),
+            ValidationErrorKind::TimestampRegression { previous, found } => write!(f, "timestamp {} is before previous block {}", found, previous),
+            ValidationErrorKind::TimestampTooFarAhead { limit, found } => write!(f, "timestamp {} is too far in the future (limit {})", found, limit),
+            ValidationErrorKind::InvalidTransaction { txid, reason } => write!(f, "transaction {} invalid: {}", txid, reason),
+            ValidationErrorKind::InvalidCoinbase { reason } => write!(f, "invalid coinbase: {}", reason),
+            ValidationErrorKind::ImmatureTransaction { txid, lock_until } => write!(f, "transaction {} is locked until {}", txid, lock_until),
+            ValidationErrorKind::GenesisMismatch { expected, found } => write!(f, "genesis {} does not match chainspec genesis {}", found, expected),
+        }
+    }
+}
+
+impl std::error::Error for ChainValidationError {}
+
+// Jumlah block terakhir yang dipakai buat median time past
+pub const MEDIAN_TIME_SPAN: usize = 11;
+
+// Timestamp block boleh di depan jam node paling banyak 2x target block time
+pub fn max_future_drift(spec: &ChainSpec) -> u64 {
+    spec.target_block_time.max(1).saturating_mul(2)
+}
+
+// Median timestamp dari `MEDIAN_TIME_SPAN` block terakhir di `chain` (ancestor block berikutnya).
+// Time lock dibandingin ke sini, bukan ke timestamp block itu sendiri yang bisa dipasang miner seenaknya.
+pub fn median_time_past(chain: &[Block]) -> u64 {
+    let mut times: Vec<u64> = chain.iter().rev().take(MEDIAN_TIME_SPAN).map(|b| b.timestamp).collect();
+    times.sort_unstable();
+    times.get(times.len() / 2).copied().unwrap_or(0)
+}
+
+// Cek header satu block terhadap parent-nya (tanpa replay transaksi).
+// `expected_difficulty` hasil retarget dari block-block sebelumnya, `max_timestamp` = jam node + drift.
+pub fn validate_header(block: &Block, previous: Option<&Block>, expected_difficulty: u64, max_timestamp: u64) -> Result<(), ValidationErrorKind> {
+    let expected_index = previous.map(|b| b.index + 1).unwrap_or(0);
+    if block.index != expected_index {
+        return Err(ValidationErrorKind::IndexMismatch { expected: expected_index, found: block.index });
+    }
+    let expected_link = previous.map(|b| b.hash.clone()).unwrap_or_else(|| String::from("0"));
+    if block.previous_hash != expected_link {
+        return Err(ValidationErrorKind::BrokenLink { expected: expected_link, found: block.previous_hash.clone() });
+    }
+    if block.difficulty != expected_difficulty {
+        return Err(ValidationErrorKind::UnexpectedDifficulty { expected: expected_difficulty, found: block.difficulty });
+    }
+    let computed = encoding::block_hash(block);
+    if computed != block.hash {
+        return Err(ValidationErrorKind::HashMismatch { computed, stored: block.hash.clone() });
+    }
+    let merkle_root = merkle::merkle_root(&block.transactions);
+    if merkle_root != block.merkle_root {
+        return Err(ValidationErrorKind::MerkleMismatch { computed: merkle_root, stored: block.merkle_root.clone() });
+    }
+    // Genesis ga di-mining, hash-nya udah dipastikan lewat chainspec
+    if previous.is_some() && !meets_target(&block.hash, block.difficulty) {
+        return Err(ValidationErrorKind::InsufficientWork { difficulty: block.difficulty });
+    }
+    if let Some(prev) = previous {
+        if block.timestamp < prev.timestamp {
+            return Err(ValidationErrorKind::TimestampRegression { previous: prev.timestamp, found: block.timestamp });
+        }
+        if block.timestamp > max_timestamp {
+            return Err(ValidationErrorKind::TimestampTooFarAhead { limit: max_timestamp, found: block.timestamp });
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_blocks(chain: &[Block], spec: &ChainSpec) -> Result<(), ChainValidationError> {
+    if let Some(genesis) = chain.first() {
+        let expected = spec.genesis_block().hash;
+        if genesis.hash != expected {
+            return Err(ChainValidationError {
+                block_index: genesis.index,
+                block_hash: genesis.hash.clone(),
+                kind: ValidationErrorKind::GenesisMismatch { expected, found: genesis.hash.clone() },
+            });
+        }
+    }
+
+    let max_timestamp = crate::now().saturating_add(max_future_drift(spec));
+    let mut wallet = Wallet::new();
+    let mut previous: Option<&Block> = None;
+    for (height, block) in chain.iter().enumerate() {
+        let fail = |kind| ChainValidationError { block_index: block.index, block_hash: block.hash.clone(), kind };
+        let expected_difficulty = next_difficulty(&chain[..height], spec);
+        validate_header(block, previous, expected_difficulty, max_timestamp).map_err(fail)?;
+        ledger::check_coinbase(block, spec).map_err(|reason| fail(ValidationErrorKind::InvalidCoinbase { reason }))?;
+        let median_time = median_time_past(&chain[..height]);
+        for tx in &block.transactions {
+            if let Some(lock_until) = tx.lock_until.filter(|_| !tx.is_mature(block.index, median_time)) {
+                return Err(fail(ValidationErrorKind::ImmatureTransaction { txid: tx.txid.clone(), lock_until }));
+            }
+            ledger::apply_transaction(&mut wallet, tx, spec)
+                .map_err(|e| fail(ValidationErrorKind::InvalidTransaction { txid: tx.txid.clone(), reason: e.to_string() }))?;
+        }
+        previous = Some(block);
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use blockchain::{Amount, Transaction, Wallet};
+
+#[test]
+fn test_decimal_round_trip_is_exact() {
+    let sol = 9;
+    let amount = Amount::parse_decimal("0.1", sol).unwrap();
+    assert_eq!(amount.units(), 100_000_000);
+    assert_eq!(amount.to_decimal_string(sol), "0.1");
+
+    // 0.1 + 0.2 pas 0.3, ga kayak f64
+    let sum = amount.checked_add(Amount::parse_decimal("0.2", sol).unwrap()).unwrap();
+    assert_eq!(sum, Amount::parse_decimal("0.3", sol).unwrap());
+
+    let wei = Amount::parse_decimal("1.000000000000000001", 18).unwrap();
+    assert_eq!(wei.units(), 1_000_000_000_000_000_001);
+    assert_eq!(Amount::parse_decimal("42", 8).unwrap().to_decimal_string(8), "42");
+}
+
+#[test]
+fn test_invalid_or_too_precise_decimals_are_rejected() {
+    assert!(Amount::parse_decimal("0.0000000001", 9).unwrap_err().contains("decimal places"));
+    assert!(Amount::parse_decimal("-1", 9).is_err());
+    assert!(Amount::parse_decimal("1e3", 9).is_err());
+    assert!(Amount::parse_decimal(".", 9).is_err());
+    assert!(Amount::parse_decimal("999999999999999999999999999999999", 18).is_err());
+}
+
+#[test]
+fn test_overflow_and_underflow_are_errors() {
+    let max = Amount::from_units(u128::MAX);
+    assert!(max.checked_add(Amount::from_units(1)).is_err());
+    assert!(Amount::ZERO.checked_sub(Amount::from_units(1)).is_err());
+
+    let mut tx = Transaction::new("a".to_string(), "b".to_string(), max, "1 SOL".parse().unwrap(), "SOL".to_string(), Amount::from_units(1));
+    assert!(tx.total_debit().is_err());
+    tx.fee = Amount::ZERO;
+    assert_eq!(tx.total_debit(), Ok(max));
+
+    let mut wallet = Wallet::new();
+    wallet.credit("a", "SOL", Amount::from_units(5)).unwrap();
+    assert!(wallet.debit("a", "SOL", Amount::from_units(6)).unwrap_err().contains("Insufficient balance"));
+    assert_eq!(wallet.get_balance("a", "SOL"), Amount::from_units(5));
+}
+
+#[test]
+fn test_amounts_serialize_as_base_unit_strings() {
+    let amount = Amount::from_units(u128::MAX);
+    let json = serde_json::to_string(&amount).unwrap();
+    assert_eq!(json, format!("\"{}\"", u128::MAX));
+    assert_eq!(serde_json::from_str::<Amount>(&json).unwrap(), amount);
+
+    assert_eq!(serde_json::from_str::<Amount>("1500").unwrap(), Amount::from_units(1500));
+    assert!(serde_json::from_str::<Amount>("1.5").is_err());
+    assert!(serde_json::from_str::<Amount>("\"1.5\"").is_err());
+}
@@ -0,0 +1,94 @@
+use blockchain::asset::FeeSchedule;
+use blockchain::chainspec::{self, ChainSpec, CHAINSPEC_FILE};
+use blockchain::ledger::{self, COINBASE_ADDRESS};
+use blockchain::{Amount, Asset, Blockchain, Peg, Transaction};
+
+mod common;
+use common::{temp_data_dir, sol};
+
+fn transfer(peg_value: &str, nonce: u64) -> Transaction {
+    let mut tx = Transaction::new(chainspec::dev_genesis_address(), "alice".to_string(), sol("1"), peg_value.parse().unwrap(), "SOL".to_string(), sol("0.11"))
+        .with_nonce(nonce);
+    tx.sign(&chainspec::dev_genesis_keypair());
+    tx
+}
+
+fn doge() -> Asset {
+    Asset {
+        symbol: "DOGE".to_string(),
+        decimals: 8,
+        fees: FeeSchedule { base_fee: Amount::from_units(100_000), per_byte_fee: Amount::ZERO },
+        supply_cap: None,
+        pegs: vec!["USD".to_string()],
+    }
+}
+
+#[test]
+fn test_peg_currency_must_be_allowed_by_asset() {
+    let peg: Peg = "15000idr".parse().unwrap();
+    assert_eq!(peg.currency, "IDR");
+    assert_eq!(peg.to_string(), "15000 IDR");
+    assert_eq!("1.50 USD".parse::<Peg>().unwrap().to_string(), "1.5 USD");
+    assert!("15000".parse::<Peg>().unwrap_err().contains("no currency"));
+    assert!("abc USD".parse::<Peg>().is_err());
+
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    let err = bc.submit_transaction(transfer("15000 EUR", 0)).unwrap_err().to_string();
+    assert!(err.contains("Unsupported peg currency EUR"), "{}", err);
+    assert!(ledger::apply_transaction(&mut bc.wallet.clone(), &transfer("15000 EUR", 0), &bc.spec).is_err());
+    // Simbol aset sendiri selalu boleh jadi peg
+    bc.submit_transaction(transfer("1 SOL", 0)).unwrap();
+
+    // Crate transaction pakai registry yang sama
+    let legacy = transaction::Transaction::new("a".to_string(), "b".to_string(), sol("1"), "1 EUR".parse().unwrap(), "SOL".to_string(), sol("0.1"));
+    assert!(legacy.validate(&bc.spec.assets).is_err());
+    let legacy = transaction::Transaction::new("a".to_string(), "b".to_string(), sol("1"), "1 USD".parse().unwrap(), "SOL".to_string(), sol("0.1"));
+    assert_eq!(legacy.validate(&bc.spec.assets), Ok(()));
+}
+
+#[test]
+fn test_registry_changes_are_persisted_and_guarded() {
+    let dir = temp_data_dir("assets");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    bc.add_block(vec![transfer("15000 IDR", 0)]);
+
+    assert!(bc.upsert_asset(doge()).unwrap());
+    assert!(!bc.upsert_asset(doge()).unwrap());
+    let invalid = Asset { symbol: "doge!".to_string(), ..doge() };
+    assert!(bc.upsert_asset(invalid).is_err());
+
+    // Fee SOL dinaikin lewatin fee transaksi lama: history jadi ga valid, jadi ditolak
+    let mut pricier = bc.spec.assets.get("SOL").unwrap().clone();
+    pricier.fees.base_fee = sol("1");
+    let err = bc.upsert_asset(pricier).unwrap_err();
+    assert!(err.contains("would invalidate the chain"), "{}", err);
+    assert!(bc.remove_asset("SOL").is_err());
+    assert_eq!(bc.spec.assets.get("SOL").unwrap().fees.base_fee, sol("0.1"));
+
+    let reopened = Blockchain::open(&dir).unwrap();
+    assert!(dir.join(CHAINSPEC_FILE).exists());
+    assert_eq!(reopened.spec.assets.get("DOGE"), Some(&doge()));
+
+    bc.remove_asset("DOGE").unwrap();
+    assert!(Blockchain::open(&dir).unwrap().spec.assets.get("DOGE").is_none());
+}
+
+#[test]
+fn test_supply_cap_limits_minting_and_rewards() {
+    let mut spec = ChainSpec::default();
+    let mut capped = spec.assets.get("SOL").unwrap().clone();
+    capped.supply_cap = Some(sol("1100.5"));
+    spec.assets.upsert(capped).unwrap();
+
+    let mut bc = Blockchain::in_memory(spec);
+    assert_eq!(bc.wallet.total_supply("SOL"), sol("1100"));
+    bc.submit_transaction(transfer("15000 IDR", 0)).unwrap();
+    assert_eq!(bc.produce_block(), Some(1));
+    // Subsidi 50 SOL dipotong sampai pas di cap
+    assert_eq!(bc.wallet.total_supply("SOL"), sol("1100.5"));
+    assert_eq!(bc.validate_chain(), Ok(()));
+
+    let mint = Transaction::new(COINBASE_ADDRESS.to_string(), "alice".to_string(), Amount::from_units(1), "1 SOL".parse().unwrap(), "SOL".to_string(), Amount::ZERO);
+    let err = ledger::apply_transaction(&mut bc.wallet.clone(), &mint, &bc.spec).unwrap_err().to_string();
+    assert!(err.contains("supply cap"), "{}", err);
+}
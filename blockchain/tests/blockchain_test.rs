@@ -1,9 +1,14 @@
-use blockchain::{Blockchain, Block};
+use blockchain::{Blockchain, Transaction};
+
+mod common;
+use common::{temp_data_dir, key, address, sol};
 
 #[tokio::test]
 async fn test_blockchain_add() {
-    let mut bc = Blockchain::new();
-    let tx = "TestTx".to_string();
+    let dir = temp_data_dir("add");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    let mut tx = Transaction::new(address("genesis"), "Bob".to_string(), sol("1"), "15000 IDR".parse().unwrap(), "SOL".to_string(), sol("0.11"));
+    tx.sign(&key("genesis"));
     bc.add_block(vec![tx.clone()]);
     assert_eq!(bc.chain.len(), 2);
     println!("Test block added: {:?}", bc.chain.last().unwrap());
@@ -11,12 +16,14 @@ async fn test_blockchain_add() {
 
 #[tokio::test]
 async fn test_transaction() {
-    let mut bc = Blockchain::new();
-    let wallet = wallet::Wallet::new();
-    if wallet.can_transfer(10).is_ok() {
-        let tx = transaction::create_transaction(&wallet, "Bob".to_string(), 10, 1.0);
+    let dir = temp_data_dir("transaction");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    if bc.wallet.get_balance(&address("genesis"), "SOL") >= sol("10") {
+        let mut tx = Transaction::new(address("genesis"), "Bob".to_string(), sol("10"), "15000 IDR".parse().unwrap(), "SOL".to_string(), sol("0.11"));
+        tx.sign(&key("genesis"));
         bc.add_block(vec![tx.clone()]);
-        println!("Test transaction: {}", tx);
+        println!("Test transaction: {:?}", tx);
     }
     assert_eq!(bc.chain.len(), 2);
-}
\ No newline at end of file
+    assert_eq!(bc.wallet.get_balance("Bob", "SOL"), sol("10"));
+}
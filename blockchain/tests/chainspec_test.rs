@@ -0,0 +1,73 @@
+use blockchain::chainspec::{ChainSpec, GenesisAllocation, CHAINSPEC_FILE};
+use blockchain::validation::ValidationErrorKind;
+use blockchain::{Amount, Blockchain};
+use std::collections::HashMap;
+
+mod common;
+use common::temp_data_dir;
+
+fn units(value: &str, decimals: u8) -> Amount {
+    Amount::parse_decimal(value, decimals).unwrap()
+}
+
+fn custom_spec() -> ChainSpec {
+    ChainSpec {
+        name: "testnet".to_string(),
+        genesis_allocations: vec![
+            GenesisAllocation { address: "treasury".to_string(), network: "BNB".to_string(), amount: units("500", 18) },
+            GenesisAllocation { address: "genesis".to_string(), network: "SOL".to_string(), amount: units("25", 9) },
+        ],
+        block_reward: HashMap::from([("SOL".to_string(), units("10", 9))]),
+        halving_interval: 2,
+        ..ChainSpec::default()
+    }
+}
+
+#[test]
+fn test_same_spec_gives_same_genesis() {
+    let (dir_a, dir_b) = (temp_data_dir("genesis-a"), temp_data_dir("genesis-b"));
+    let a = Blockchain::open_with_spec(&dir_a, custom_spec()).unwrap();
+    let b = Blockchain::open_with_spec(&dir_b, custom_spec()).unwrap();
+    assert_eq!(a.chain[0].hash, b.chain[0].hash);
+    assert_ne!(a.chain[0].hash, ChainSpec::default().genesis_block().hash);
+    assert_eq!(a.wallet.get_balance("treasury", "BNB"), units("500", 18));
+    assert_eq!(a.wallet.get_balance("genesis", "SOL"), units("25", 9));
+}
+
+#[test]
+fn test_spec_file_is_loaded_from_data_dir() {
+    let dir = temp_data_dir("specfile");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join(CHAINSPEC_FILE), serde_json::to_string_pretty(&custom_spec()).unwrap()).unwrap();
+
+    let bc = Blockchain::open(&dir).unwrap();
+    assert_eq!(bc.spec, custom_spec());
+    assert_eq!(bc.chain[0].hash, custom_spec().genesis_block().hash);
+}
+
+#[test]
+fn test_reopen_with_different_spec_fails() {
+    let dir = temp_data_dir("mismatch");
+    Blockchain::open_with_spec(&dir, ChainSpec::default()).unwrap();
+    assert!(Blockchain::open_with_spec(&dir, custom_spec()).is_err());
+}
+
+#[test]
+fn test_block_reward_halves() {
+    let spec = custom_spec();
+    assert_eq!(spec.block_subsidy(1, "SOL"), units("10", 9));
+    assert_eq!(spec.block_subsidy(2, "SOL"), units("5", 9));
+    assert_eq!(spec.block_subsidy(5, "SOL"), units("2.5", 9));
+    // Network tanpa reward ga dapet subsidy
+    assert_eq!(spec.block_subsidy(1, "BTC"), Amount::ZERO);
+}
+
+#[test]
+fn test_foreign_genesis_fails_validation() {
+    let dir = temp_data_dir("foreign");
+    let mut bc = Blockchain::open_with_spec(&dir, ChainSpec::default()).unwrap();
+    bc.chain[0] = custom_spec().genesis_block();
+    let err = bc.validate_chain().unwrap_err();
+    assert_eq!(err.block_index, 0);
+    assert!(matches!(err.kind, ValidationErrorKind::GenesisMismatch { .. }));
+}
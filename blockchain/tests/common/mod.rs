@@ -0,0 +1,69 @@
+// Helper bersama buat semua integration test, dipakai lewat `mod common;`.
+// Tiap file test dikompilasi sendiri-sendiri, jadi helper yang ga kepake di satu file jangan di-warn.
+#![allow(dead_code)]
+
+use blockchain::{chainspec, mining, Amount, Block, Blockchain, Transaction};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use wallet::encryption::{self, Keypair};
+
+// Folder data sementara buat satu test, dihapus lagi pas guard-nya di-drop
+pub struct TempDir(PathBuf);
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+pub fn temp_data_dir(name: &str) -> TempDir {
+    TempDir(std::env::temp_dir().join(format!("blockchain-{}-{}", name, uuid::Uuid::new_v4())))
+}
+
+// "genesis" = akun alokasi genesis devnet, nama lain jadi seed akun test
+pub fn key(name: &str) -> Keypair {
+    if name == "genesis" {
+        chainspec::dev_genesis_keypair()
+    } else {
+        chainspec::seed_keypair(name)
+    }
+}
+
+pub fn address(name: &str) -> String {
+    encryption::get_address(&key(name).public)
+}
+
+pub fn sol(value: &str) -> Amount {
+    Amount::parse_decimal(value, 9).unwrap()
+}
+
+// Transfer SOL bertanda tangan dengan peg dan fee standar
+pub fn transfer(from: &str, to: &str, amount: &str, nonce: u64) -> Transaction {
+    transfer_with_fee(from, to, amount, "0.11", nonce)
+}
+
+pub fn transfer_with_fee(from: &str, to: &str, amount: &str, fee: &str, nonce: u64) -> Transaction {
+    let mut tx = Transaction::new(address(from), address(to), sol(amount), "15000 IDR".parse().unwrap(), "SOL".to_string(), sol(fee))
+        .with_nonce(nonce);
+    tx.sign(&key(from));
+    tx
+}
+
+// Mining block di atas `parent` (ga harus tip chain aktif), buat bikin cabang saingan
+pub fn mine_on(bc: &Blockchain, parent: &Block, transactions: Vec<Transaction>) -> Block {
+    mining::mine_block(parent.index as u32 + 1, parent.hash.clone(), parent.timestamp, transactions, bc.difficulty, &bc.spec, &bc.wallet)
+}
@@ -0,0 +1,89 @@
+use blockchain::chainspec::{self, ChainSpec};
+use blockchain::difficulty::{meets_target, next_difficulty};
+use blockchain::miner::MinerState;
+use blockchain::validation::{self, ValidationErrorKind};
+use blockchain::{mining, Amount, Block, Blockchain, Transaction};
+
+mod common;
+use common::temp_data_dir;
+
+fn spec() -> ChainSpec {
+    ChainSpec { difficulty: 1000, target_block_time: 10, difficulty_window: 5, ..ChainSpec::default() }
+}
+
+// Genesis + `count` block dengan jarak waktu tetap
+fn synthetic_chain(count: u64, solvetime: u64, difficulty: u64) -> Vec<Block> {
+    let spec = spec();
+    let mut chain = vec![spec.genesis_block()];
+    for i in 1..=count {
+        let mut block = Block::new(i, vec![], chain.last().unwrap().hash.clone());
+        block.timestamp = spec.genesis_timestamp + i * solvetime;
+        block.difficulty = difficulty;
+        chain.push(block);
+    }
+    chain
+}
+
+#[test]
+fn test_meets_target_matches_hex_prefix() {
+    assert!(meets_target("00ffffffffffffffffff", 256));
+    assert!(!meets_target("0100000000000000ffff", 256));
+    assert!(meets_target("ffffffffffffffff", 1));
+    assert!(!meets_target("nothex", 1));
+}
+
+#[test]
+fn test_no_retarget_before_window_fills() {
+    let chain = synthetic_chain(5, 1, 1000);
+    assert_eq!(next_difficulty(&chain, &spec()), 1000);
+}
+
+#[test]
+fn test_retarget_follows_block_time() {
+    assert_eq!(next_difficulty(&synthetic_chain(10, 10, 1000), &spec()), 1000);
+    assert_eq!(next_difficulty(&synthetic_chain(10, 5, 1000), &spec()), 2000);
+    assert_eq!(next_difficulty(&synthetic_chain(10, 20, 1000), &spec()), 500);
+    // Naik 25%, bukan kelipatan 16 kayak hitungan nol hex
+    assert_eq!(next_difficulty(&synthetic_chain(10, 8, 1000), &spec()), 1250);
+}
+
+#[test]
+fn test_far_future_block_is_rejected_before_retarget() {
+    let spec = spec();
+    let chain = synthetic_chain(10, 10, 1000);
+    let now = chrono::Utc::now().timestamp() as u64;
+    let max_timestamp = now + validation::max_future_drift(&spec);
+    let stamped = |timestamp| {
+        let mut block = Block::new(11, vec![], chain.last().unwrap().hash.clone());
+        block.timestamp = timestamp;
+        block.difficulty = 1000;
+        mining::mine_candidate(block, 1, &MinerState::default()).unwrap()
+    };
+
+    // Kalau lolos, solvetime-nya ke-clamp 6x target dan difficulty turun
+    let future = stamped(now + 3600);
+    let mut stretched = chain.clone();
+    stretched.push(future.clone());
+    assert!(next_difficulty(&stretched, &spec) < 1000);
+    assert_eq!(
+        validation::validate_header(&future, chain.last(), 1000, max_timestamp),
+        Err(ValidationErrorKind::TimestampTooFarAhead { limit: max_timestamp, found: now + 3600 })
+    );
+    assert_eq!(validation::validate_header(&stamped(now), chain.last(), 1000, max_timestamp), Ok(()));
+}
+
+#[test]
+fn test_block_with_wrong_difficulty_is_rejected() {
+    let dir = temp_data_dir("difficulty");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    let mut tx = Transaction::new(chainspec::dev_genesis_address(), "alice".to_string(), Amount::from_units(1_000_000_000), "15000 IDR".parse().unwrap(), "SOL".to_string(), Amount::from_units(110_000_000));
+    tx.sign(&chainspec::dev_genesis_keypair());
+    bc.add_block(vec![tx]);
+    assert_eq!(bc.chain[1].difficulty, bc.spec.difficulty);
+    assert_eq!(bc.validate_chain(), Ok(()));
+
+    bc.chain[1].difficulty = 1;
+    let err = bc.validate_chain().unwrap_err();
+    assert_eq!(err.block_index, 1);
+    assert_eq!(err.kind, ValidationErrorKind::UnexpectedDifficulty { expected: 256, found: 1 });
+}
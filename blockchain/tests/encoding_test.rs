@@ -0,0 +1,59 @@
+use blockchain::encoding::{self, ENCODING_VERSION};
+use blockchain::status::TxStatus;
+use blockchain::{Amount, Blockchain, Block};
+
+mod common;
+use common::{temp_data_dir, transfer};
+
+#[test]
+fn test_mined_hash_is_reproducible() {
+    let dir = temp_data_dir("encoding");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    bc.add_block(vec![transfer("genesis", "alice", "1.0", 0)]);
+    let block = bc.chain.last().unwrap();
+    assert_eq!(encoding::block_hash(block), block.hash);
+
+    let mut recomputed = block.clone();
+    recomputed.calculate_hash();
+    assert_eq!(recomputed.hash, block.hash);
+
+    // Lewat JSON (kayak di block store) hash-nya tetap sama
+    let roundtrip: Block = serde_json::from_str(&serde_json::to_string(block).unwrap()).unwrap();
+    assert_eq!(encoding::block_hash(&roundtrip), block.hash);
+}
+
+#[test]
+fn test_header_commits_to_nonce_and_timestamp() {
+    let dir = temp_data_dir("header");
+    let bc = Blockchain::open(&dir).unwrap();
+    let mut block = Block::new(1, vec![transfer("genesis", "alice", "1.0", 0)], bc.chain[0].hash.clone());
+    let original = encoding::block_hash(&block);
+
+    block.nonce += 1;
+    assert_ne!(encoding::block_hash(&block), original);
+    block.nonce -= 1;
+    block.timestamp += 1;
+    assert_ne!(encoding::block_hash(&block), original);
+}
+
+#[test]
+fn test_transaction_encoding_is_canonical() {
+    let tx = transfer("genesis", "alice", "0.1", 0);
+    let encoded = encoding::encode_transaction(&tx);
+    assert_eq!(encoded[0], ENCODING_VERSION);
+
+    // Status cuma catatan lokal, ga ngubah encoding
+    let mut confirmed = tx.clone();
+    confirmed.status = TxStatus::Included { block: 1 };
+    assert_eq!(encoding::encode_transaction(&confirmed), encoded);
+
+    // Signature kosong beda sama ga ada signature
+    let mut signed = tx.clone();
+    signed.signature = Some(String::new());
+    assert_ne!(encoding::encode_transaction(&signed), encoded);
+
+    // Beda satu lamport aja udah beda encoding
+    let mut one_more = tx.clone();
+    one_more.fee = one_more.fee.checked_add(Amount::from_units(1)).unwrap();
+    assert_ne!(encoding::encode_transaction(&one_more), encoded);
+}
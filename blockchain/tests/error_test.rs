@@ -0,0 +1,116 @@
+use actix_web::http::StatusCode;
+use actix_web::{test as actix_test, web, App};
+use blockchain::chainspec::ChainSpec;
+use blockchain::error::{self, ApiError, ErrorCode};
+use blockchain::network::{add_single_transaction, get_block, get_chain_info, get_history};
+use blockchain::{Blockchain, TxError};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+mod common;
+use common::{address, transfer};
+
+fn shared() -> Arc<Mutex<Blockchain>> {
+    Arc::new(Mutex::new(Blockchain::in_memory(ChainSpec::default())))
+}
+
+macro_rules! app {
+    ($chain:expr) => {
+        actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new($chain.clone()))
+                .app_data(error::json_config())
+                .app_data(error::query_config())
+                .app_data(error::path_config())
+                .service(add_single_transaction)
+                .service(get_block)
+                .service(get_chain_info)
+                .service(get_history)
+                .default_service(web::to(error::route_not_found)),
+        )
+        .await
+    };
+}
+
+#[test]
+fn test_core_errors_map_to_codes() {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    let code = |e: TxError| ApiError::from(e).code;
+
+    assert_eq!(code(bc.submit_transaction(transfer("alice", "bob", "5", 0)).unwrap_err()), ErrorCode::InsufficientBalance);
+    let mut forged = transfer("genesis", "bob", "5", 0);
+    forged.signature = transfer("alice", "bob", "5", 0).signature;
+    assert_eq!(code(bc.submit_transaction(forged).unwrap_err()), ErrorCode::InvalidSignature);
+    assert_eq!(code(bc.submit_transaction(transfer("genesis", "bob", "5", 3)).unwrap_err()), ErrorCode::Validation);
+
+    let first = transfer("genesis", "bob", "5", 0);
+    bc.submit_transaction(first.clone()).unwrap();
+    assert_eq!(code(bc.submit_transaction(first).unwrap_err()), ErrorCode::Conflict);
+    // Nonce yang sama dari sender yang sama juga konflik, walaupun txid-nya beda
+    assert_eq!(code(bc.submit_transaction(transfer("genesis", "carol", "1", 0)).unwrap_err()), ErrorCode::Conflict);
+
+    assert_eq!(ErrorCode::InsufficientBalance.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(ErrorCode::RateLimited.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[actix_web::test]
+async fn test_errors_are_json_with_stable_codes() {
+    let chain = shared();
+    let app = app!(chain);
+
+    let resp = actix_test::call_service(&app, actix_test::TestRequest::get().uri("/block/99").to_request()).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["code"], "not_found");
+    assert_eq!(body["message"], "Block not found");
+    assert!(body["details"].is_null());
+
+    let resp = actix_test::call_service(&app, actix_test::TestRequest::get().uri("/nope").to_request()).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["code"], "not_found");
+
+    // Extractor actix juga balikin JSON
+    let resp = actix_test::call_service(&app, actix_test::TestRequest::get().uri("/block/abc").to_request()).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["code"], "validation");
+    let req = actix_test::TestRequest::post().uri("/transaction/single").insert_header(("content-type", "application/json")).set_payload("{oops").to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["code"], "validation");
+    let resp = actix_test::call_service(&app, actix_test::TestRequest::get().uri(&format!("/history/{}?cursor=x", address("alice"))).to_request()).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // Saldo kurang -> 422, txid ikut di details
+    let tx = transfer("alice", "bob", "5", 0);
+    let req = actix_test::TestRequest::post().uri("/transaction/single").set_json(&tx).to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["code"], "insufficient_balance");
+    assert_eq!(body["details"]["txid"], tx.txid);
+
+    let req = actix_test::TestRequest::post().uri("/transaction/single").set_json(transfer("genesis", "alice", "5", 0)).to_request();
+    assert_eq!(actix_test::call_service(&app, req).await.status(), StatusCode::ACCEPTED);
+}
+
+#[actix_web::test]
+async fn test_poisoned_lock_does_not_break_handlers() {
+    let chain = shared();
+    let poisoner = chain.clone();
+    let _ = std::thread::spawn(move || {
+        let _guard = poisoner.lock().unwrap();
+        panic!("worker died while holding the chain");
+    })
+    .join();
+    assert!(chain.is_poisoned());
+
+    let app = app!(chain);
+    let resp = actix_test::call_service(&app, actix_test::TestRequest::get().uri("/chain/info").to_request()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["height"], 0);
+    assert!(!chain.is_poisoned());
+}
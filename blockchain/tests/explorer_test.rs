@@ -0,0 +1,86 @@
+use blockchain::chainspec::{self, ChainSpec};
+use blockchain::explorer::{self, SearchResult, MAX_PAGE_SIZE};
+use blockchain::{merkle, Blockchain, Transaction};
+
+mod common;
+use common::{address, sol, transfer};
+
+// Genesis + 4 block, transfer ke alice ada di block 1
+fn explored() -> (Blockchain, Transaction) {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    let tx = transfer("genesis", "alice", "1", 0);
+    bc.add_block(vec![tx.clone()]);
+    for _ in 0..3 {
+        bc.add_block(vec![]);
+    }
+    (bc, tx)
+}
+
+#[test]
+fn test_block_responses_include_header_merkle_and_confirmations() {
+    let (bc, tx) = explored();
+    let block = explorer::block(&bc, 1).unwrap();
+    assert_eq!(block.summary.confirmations, 4);
+    assert_eq!(block.summary.transaction_count, block.transactions.len());
+    assert_eq!(block.merkle_leaves, block.transactions.iter().map(merkle::transaction_hash).collect::<Vec<_>>());
+    assert!(block.transactions.iter().any(|t| t.txid == tx.txid));
+
+    let json = serde_json::to_value(&block).unwrap();
+    assert_eq!(json["nonce"], bc.chain[1].nonce);
+    assert_eq!(json["merkle_root"], bc.chain[1].merkle_root);
+    assert_eq!(json["confirmations"], 4);
+
+    let by_hash = explorer::block_by_hash(&bc, &bc.chain[1].hash).unwrap();
+    assert_eq!(by_hash.summary.header, bc.chain[1].header());
+    assert!(explorer::block(&bc, 5).is_none());
+    assert_eq!(explorer::latest_block(&bc).unwrap().summary.confirmations, 1);
+
+    let info = explorer::chain_info(&bc);
+    assert_eq!(info.height, 4);
+    assert_eq!(info.tip_hash, bc.chain[4].hash);
+    assert_eq!(info.total_work, bc.total_work());
+}
+
+#[test]
+fn test_blocks_are_paginated() {
+    let (bc, _) = explored();
+    let page = explorer::blocks(&bc, 1, 2);
+    assert_eq!(page.blocks.iter().map(|b| b.header.index).collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(page.next, Some(3));
+    let last = explorer::blocks(&bc, 3, 10);
+    assert_eq!(last.blocks.len(), 2);
+    assert_eq!(last.next, None);
+    assert!(explorer::blocks(&bc, 99, 10).blocks.is_empty());
+    assert_eq!(explorer::blocks(&bc, 0, 0).blocks.len(), 1);
+    assert!(explorer::blocks(&bc, 0, usize::MAX).blocks.len() <= MAX_PAGE_SIZE);
+}
+
+#[test]
+fn test_search_resolves_blocks_transactions_and_addresses() {
+    let (bc, tx) = explored();
+    match explorer::search(&bc, "2") {
+        Some(SearchResult::Block(block)) => assert_eq!(block.summary.header.index, 2),
+        other => panic!("unexpected {:?}", other),
+    }
+    match explorer::search(&bc, &bc.chain[3].hash) {
+        Some(SearchResult::Block(block)) => assert_eq!(block.summary.header.index, 3),
+        other => panic!("unexpected {:?}", other),
+    }
+    match explorer::search(&bc, &tx.txid) {
+        Some(SearchResult::Transaction(found)) => assert_eq!(found.txid, tx.txid),
+        other => panic!("unexpected {:?}", other),
+    }
+    match explorer::search(&bc, &address("alice")) {
+        Some(SearchResult::Address(summary)) => {
+            assert_eq!(summary.balances.get("SOL"), Some(&sol("1")));
+            assert_eq!(summary.transaction_count, 1);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    let json = serde_json::to_value(explorer::search(&bc, &chainspec::dev_genesis_address()).unwrap()).unwrap();
+    assert_eq!(json["type"], "address");
+    assert_eq!(json["result"]["nonce"], 1);
+
+    assert!(explorer::search(&bc, "no-such-thing").is_none());
+    assert!(explorer::search(&bc, "42").is_none());
+}
@@ -0,0 +1,92 @@
+use blockchain::chainspec::{ChainSpec, GenesisAllocation};
+use blockchain::faucet::{Faucet, FaucetConfig};
+use blockchain::{Blockchain, TxError};
+use std::net::IpAddr;
+
+mod common;
+use common::{address, sol};
+
+fn ip(value: &str) -> Option<IpAddr> {
+    Some(value.parse().unwrap())
+}
+
+// Devnet plus alokasi genesis buat akun faucet test (seed "faucet")
+fn spec() -> ChainSpec {
+    let mut spec = ChainSpec::default();
+    spec.genesis_allocations.push(GenesisAllocation { address: address("faucet"), network: "SOL".to_string(), amount: sol("1000") });
+    spec
+}
+
+fn config() -> FaucetConfig {
+    FaucetConfig { enabled: true, seed: "faucet".to_string(), drip: "2".to_string(), ..FaucetConfig::default() }
+}
+
+fn with_faucet() -> Blockchain {
+    let mut bc = Blockchain::in_memory(spec());
+    bc.faucet = Some(Faucet::new(config(), &bc.spec).unwrap());
+    bc
+}
+
+#[test]
+fn test_faucet_pays_from_its_genesis_allocation() {
+    let mut bc = with_faucet();
+    let faucet_address = address("faucet");
+    assert_eq!(bc.wallet.get_balance(&faucet_address, "SOL"), sol("1000"));
+
+    let tx = bc.request_faucet(&address("alice"), ip("10.0.0.1")).unwrap();
+    assert_eq!(tx.from, faucet_address);
+    assert!(tx.validate().is_ok());
+    let second = bc.request_faucet(&address("bob"), ip("10.0.0.2")).unwrap();
+    assert_eq!(second.nonce, tx.nonce + 1);
+
+    assert_eq!(bc.produce_block(), Some(1));
+    assert_eq!(bc.wallet.get_balance(&address("alice"), "SOL"), sol("2"));
+    assert_eq!(bc.wallet.get_balance(&address("bob"), "SOL"), sol("2"));
+    // Cuma pindah saldo dari alokasi faucet, ga ada yang dicetak
+    let spent = sol("4").checked_add(tx.fee).unwrap().checked_add(second.fee).unwrap();
+    assert_eq!(bc.wallet.get_balance(&faucet_address, "SOL"), sol("1000").checked_sub(spent).unwrap());
+    assert_eq!(bc.validate_chain(), Ok(()));
+}
+
+#[test]
+fn test_faucet_is_rate_limited_per_address_and_ip() {
+    let mut bc = with_faucet();
+    bc.request_faucet(&address("alice"), ip("10.0.0.1")).unwrap();
+
+    let err = bc.request_faucet(&address("alice"), ip("10.0.0.9")).unwrap_err();
+    assert!(matches!(&err, TxError::RateLimited(reason) if reason.contains("already received faucet funds")), "{:?}", err);
+    let err = bc.request_faucet(&address("bob"), ip("10.0.0.1")).unwrap_err();
+    assert!(matches!(&err, TxError::RateLimited(reason) if reason.contains("from this IP")), "{:?}", err);
+    assert_eq!(bc.mempool.len(), 1);
+
+    // Cooldown dihitung dari waktu permintaan, lewat dari itu boleh lagi
+    let faucet = bc.faucet.as_ref().unwrap();
+    let later = chrono::Utc::now().timestamp() as u64 + 24 * 3600;
+    assert!(faucet.check(&address("alice"), ip("10.0.0.1"), later).is_ok());
+
+    assert!(bc.request_faucet("not-an-address", None).unwrap_err().to_string().contains("Invalid address"));
+    bc.request_faucet(&address("carol"), None).unwrap();
+}
+
+#[test]
+fn test_faucet_only_runs_on_dev_and_test_networks() {
+    let spec = spec();
+    assert!(Faucet::new(FaucetConfig::default(), &spec).is_err());
+    // Ga ada seed bawaan, kunci faucet harus dari operator
+    let unseeded = FaucetConfig { seed: String::new(), ..config() };
+    assert!(Faucet::new(unseeded, &spec).unwrap_err().contains("FAUCET_SEED is required"));
+
+    let mut mainnet = spec.clone();
+    mainnet.name = "mainnet".to_string();
+    let err = Faucet::new(config(), &mainnet).unwrap_err();
+    assert!(err.contains("only available on dev and test networks"), "{}", err);
+
+    let mut testnet = spec.clone();
+    testnet.name = "testnet-2".to_string();
+    assert!(Faucet::new(config(), &testnet).is_ok());
+
+    // Kunci faucet harus punya alokasi genesis
+    let unfunded = FaucetConfig { seed: "someone-else".to_string(), ..config() };
+    assert!(Faucet::new(unfunded, &spec).unwrap_err().contains("has no SOL genesis allocation"));
+    assert!(Blockchain::in_memory(spec).request_faucet(&address("alice"), None).is_err());
+}
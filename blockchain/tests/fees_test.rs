@@ -0,0 +1,96 @@
+use blockchain::chainspec::ChainSpec;
+use blockchain::fees::{self, TYPICAL_TRANSFER_BYTES};
+use blockchain::mempool::Mempool;
+use blockchain::{ledger, Amount, Blockchain, Transaction, Wallet};
+
+mod common;
+use common::{key, address, sol};
+
+// Transfer dengan fee persis `minimum_fee` dikali `percent`
+fn transfer_paying(spec: &ChainSpec, from: &str, nonce: u64, percent: u128) -> Transaction {
+    let mut tx = Transaction::new(address(from), address("bob"), sol("1"), "15000 IDR".parse().unwrap(), "SOL".to_string(), Amount::ZERO)
+        .with_nonce(nonce);
+    tx.fee = fees::minimum_fee(&spec.assets, &tx).unwrap().checked_mul(percent).unwrap().div_floor(100);
+    tx.sign(&key(from));
+    tx
+}
+
+#[test]
+fn test_minimum_fee_is_base_plus_size() {
+    let spec = ChainSpec::default();
+    let tx = transfer_paying(&spec, "genesis", 0, 100);
+    let size = fees::transaction_size(&tx);
+    assert!(size <= TYPICAL_TRANSFER_BYTES, "{}", size);
+    // Ukuran ga tergantung nilai fee, jadi client bisa ngitung fee sebelum tanda tangan
+    let mut cheaper = tx.clone();
+    cheaper.fee = Amount::ZERO;
+    assert_eq!(fees::transaction_size(&cheaper), size);
+
+    let schedule = &spec.assets.require("SOL").unwrap().fees;
+    let per_byte = schedule.per_byte_fee.checked_mul(size as u128).unwrap();
+    assert_eq!(tx.fee, schedule.base_fee.checked_add(per_byte).unwrap());
+    assert_eq!(fees::check(&spec.assets, &tx), Ok(()));
+}
+
+#[test]
+fn test_underpaid_or_unknown_network_is_rejected_everywhere() {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    let underpaid = transfer_paying(&bc.spec, "genesis", 0, 99);
+    let err = bc.submit_transaction(underpaid.clone()).unwrap_err().to_string();
+    assert!(err.contains("below the minimum"), "{}", err);
+
+    let mut wallet = bc.wallet.clone();
+    assert!(ledger::apply_transaction(&mut wallet, &underpaid, &bc.spec).is_err());
+    assert_eq!(wallet.get_balance(&address("genesis"), "SOL"), sol("100"));
+
+    let mut unknown = Transaction::new(address("genesis"), address("bob"), sol("1"), "15000 IDR".parse().unwrap(), "DOGE".to_string(), sol("1"));
+    unknown.sign(&key("genesis"));
+    assert!(bc.submit_transaction(unknown).unwrap_err().to_string().contains("Unsupported network"));
+
+    // Yang bayar pas minimum masuk block, fee yang didebit sama persis dengan yang dicek
+    let paid = transfer_paying(&bc.spec, "genesis", 0, 100);
+    bc.submit_transaction(paid.clone()).unwrap();
+    assert_eq!(bc.produce_block(), Some(1));
+    let spent = paid.total_debit().unwrap();
+    assert_eq!(bc.wallet.get_balance(&address("genesis"), "SOL"), sol("100").checked_sub(spent).unwrap());
+}
+
+#[test]
+fn test_congestion_raises_mempool_fee() {
+    let mut spec = ChainSpec::default();
+    spec.fees.congestion_threshold = 1;
+    spec.fees.max_congestion_percent = 300;
+    assert_eq!(spec.fees.congestion_percent(0), 100);
+    assert_eq!(spec.fees.congestion_percent(1), 100);
+    assert_eq!(spec.fees.congestion_percent(2), 200);
+    assert_eq!(spec.fees.congestion_percent(50), 300);
+
+    let mut wallet = Wallet::new();
+    wallet.credit(&address("alice"), "SOL", sol("100")).unwrap();
+    let mut mempool = Mempool::default();
+    mempool.insert(transfer_paying(&spec, "alice", 0, 100), &wallet, &spec, 100).unwrap();
+    mempool.insert(transfer_paying(&spec, "alice", 1, 100), &wallet, &spec, 100).unwrap();
+    // Dua transaksi pending, threshold 1: butuh 2x fee minimum
+    let err = mempool.insert(transfer_paying(&spec, "alice", 2, 150), &wallet, &spec, 100).unwrap_err().to_string();
+    assert!(err.contains("200% congestion"), "{}", err);
+    mempool.insert(transfer_paying(&spec, "alice", 2, 200), &wallet, &spec, 100).unwrap();
+
+    // Ledger ga peduli congestion, cukup fee minimum
+    let mut chain_wallet = wallet.clone();
+    ledger::apply_transaction(&mut chain_wallet, &transfer_paying(&spec, "alice", 0, 100), &spec).unwrap();
+}
+
+#[test]
+fn test_estimate_reports_policy_and_congestion() {
+    let spec = ChainSpec::default();
+    let estimate = spec.fees.estimate(&spec.assets, "SOL", 200, 0).unwrap();
+    assert_eq!(estimate.minimum_fee, sol("0.102"));
+    assert_eq!(estimate.recommended_fee, sol("0.102"));
+    assert_eq!(estimate.display_recommended_fee, "0.102");
+
+    let busy = spec.fees.estimate(&spec.assets, "SOL", 200, spec.fees.congestion_threshold * 2).unwrap();
+    assert_eq!(busy.congestion_percent, 200);
+    assert_eq!(busy.recommended_fee, sol("0.204"));
+
+    assert!(spec.fees.estimate(&spec.assets, "DOGE", 200, 0).is_err());
+}
@@ -0,0 +1,97 @@
+use blockchain::forkchoice::BlockOutcome;
+use blockchain::{ledger, Amount, Block, Blockchain};
+
+mod common;
+use common::{temp_data_dir, address, mine_on, sol, transfer};
+
+#[test]
+fn test_heavier_branch_triggers_reorg() {
+    let dir = temp_data_dir("reorg");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    let genesis = bc.chain[0].clone();
+    let dropped_tx = transfer("genesis", "alice", "10.0", 0);
+    bc.add_block(vec![dropped_tx.clone()]);
+    let old_tip = bc.chain[1].hash.clone();
+
+    // Cabang saingan dari genesis (block kosong): awalnya seri, belum ganti chain
+    let b1 = mine_on(&bc, &genesis, vec![]);
+    assert!(matches!(bc.accept_block(b1.clone()).unwrap(), BlockOutcome::SideBranch { height: 1, .. }));
+    assert_eq!(bc.chain[1].hash, old_tip);
+    assert_eq!(bc.tips().len(), 2);
+
+    let b2 = mine_on(&bc, &b1, vec![]);
+    match bc.accept_block(b2.clone()).unwrap() {
+        BlockOutcome::Reorganized(event) => {
+            assert_eq!(event.depth, 1);
+            assert_eq!(event.fork_height, 0);
+            assert_eq!(event.old_tip, old_tip);
+            assert_eq!(event.new_tip, b2.hash);
+            assert_eq!(event.dropped.len(), 1);
+            assert_eq!(event.dropped[0].txid, dropped_tx.txid);
+        }
+        other => panic!("expected reorg, got {:?}", other),
+    }
+
+    assert_eq!(bc.chain.len(), 3);
+    assert_eq!(bc.chain.last().unwrap().hash, b2.hash);
+    assert_eq!(bc.wallet.get_balance(&address("alice"), "SOL"), Amount::ZERO);
+    assert_eq!(bc.wallet.get_balance(&address("genesis"), "SOL"), sol("100"));
+    assert!(bc.index.transaction(&dropped_tx.txid).is_none());
+    // Transaksi yang ke-drop balik ke mempool buat di-mining ulang
+    assert!(bc.mempool.contains(&dropped_tx.txid));
+    assert_eq!(bc.validate_chain(), Ok(()));
+
+    let replayed = ledger::replay(&bc.chain, &bc.spec).unwrap();
+    assert_eq!(replayed.get_balance(&address("genesis"), "SOL"), bc.wallet.get_balance(&address("genesis"), "SOL"));
+
+    // Setelah restart, cabang yang sama kepilih lagi
+    let reopened = Blockchain::open(&dir).unwrap();
+    assert_eq!(reopened.chain.last().unwrap().hash, b2.hash);
+    assert_eq!(reopened.tips().len(), 2);
+}
+
+#[test]
+fn test_invalid_side_branch_is_rejected() {
+    let dir = temp_data_dir("invalid-branch");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    let genesis = bc.chain[0].clone();
+    bc.add_block(vec![transfer("genesis", "alice", "10.0", 0)]);
+
+    // Genesis cuma punya 100 SOL
+    let overspend = mine_on(&bc, &genesis, vec![transfer("genesis", "mallory", "500.0", 0)]);
+    assert!(bc.accept_block(overspend).is_err());
+    assert_eq!(bc.tips().len(), 1);
+
+    let orphan = mine_on(&bc, &Block::new(5, vec![], "unknown".to_string()), vec![]);
+    assert!(bc.accept_block(orphan).is_err());
+}
+
+#[test]
+fn test_duplicate_block_is_ignored() {
+    let dir = temp_data_dir("duplicate");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    bc.add_block(vec![transfer("genesis", "alice", "1.0", 0)]);
+    let tip = bc.chain[1].clone();
+    assert!(matches!(bc.accept_block(tip).unwrap(), BlockOutcome::AlreadyKnown));
+    assert_eq!(bc.chain.len(), 2);
+    assert!(bc.total_work() > 0);
+}
+
+#[test]
+fn test_conflicting_nonce_is_not_returned_to_mempool() {
+    let dir = temp_data_dir("conflict");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    let genesis = bc.chain[0].clone();
+    let dropped_tx = transfer("genesis", "alice", "10.0", 0);
+    bc.add_block(vec![dropped_tx.clone()]);
+
+    // Cabang saingan pakai nonce 0 genesis buat transfer lain
+    let b1 = mine_on(&bc, &genesis, vec![transfer("genesis", "bob", "5.0", 0)]);
+    bc.accept_block(b1.clone()).unwrap();
+    let b2 = mine_on(&bc, &b1, vec![]);
+    assert!(matches!(bc.accept_block(b2).unwrap(), BlockOutcome::Reorganized(_)));
+
+    assert!(!bc.mempool.contains(&dropped_tx.txid));
+    assert_eq!(bc.wallet.get_nonce(&address("genesis")), 1);
+    assert_eq!(bc.wallet.get_balance(&address("bob"), "SOL"), sol("5"));
+}
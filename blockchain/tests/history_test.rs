@@ -0,0 +1,74 @@
+use blockchain::chainspec::ChainSpec;
+use blockchain::history::{self, Direction, HistoryQuery, Order, StatusFilter};
+use blockchain::status::{TxStatus, CONFIRMATION_DEPTH};
+use blockchain::{Blockchain, Transaction};
+
+mod common;
+use common::{address, transfer};
+
+// Block 1..=5 masing-masing satu transfer genesis -> alice, block 6 alice -> bob, plus satu pending
+fn busy() -> Blockchain {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    for nonce in 0..5 {
+        bc.add_block(vec![transfer("genesis", "alice", &(nonce + 1).to_string(), nonce)]);
+    }
+    bc.add_block(vec![transfer("alice", "bob", "1", 0)]);
+    bc.submit_transaction(transfer("alice", "bob", "2", 1)).unwrap();
+    bc
+}
+
+fn amounts(txs: &[Transaction]) -> Vec<String> {
+    txs.iter().map(|tx| tx.amount.to_decimal_string(9)).collect()
+}
+
+#[test]
+fn test_history_is_paginated_with_cursor() {
+    let bc = busy();
+    let alice = address("alice");
+    let mut query = HistoryQuery { limit: Some(3), ..HistoryQuery::default() };
+
+    // Default paling baru duluan, pending di depan
+    let first = history::address_history(&bc, &alice, &query).unwrap();
+    assert_eq!(amounts(&first.transactions), vec!["2", "1", "5"]);
+    assert_eq!(first.transactions[0].status, TxStatus::Pending);
+    query.cursor = first.next_cursor.clone();
+    let second = history::address_history(&bc, &alice, &query).unwrap();
+    assert_eq!(amounts(&second.transactions), vec!["4", "3", "2"]);
+    query.cursor = second.next_cursor.clone();
+    let third = history::address_history(&bc, &alice, &query).unwrap();
+    assert_eq!(amounts(&third.transactions), vec!["1"]);
+    assert_eq!(third.next_cursor, None);
+
+    let asc = HistoryQuery { order: Order::Asc, limit: Some(4), ..HistoryQuery::default() };
+    let page = history::address_history(&bc, &alice, &asc).unwrap();
+    assert_eq!(amounts(&page.transactions), vec!["1", "2", "3", "4"]);
+    let rest = HistoryQuery { cursor: page.next_cursor, ..asc };
+    assert_eq!(amounts(&history::address_history(&bc, &alice, &rest).unwrap().transactions), vec!["5", "1", "2"]);
+
+    let bad = HistoryQuery { cursor: Some("nope".to_string()), ..HistoryQuery::default() };
+    assert!(history::address_history(&bc, &alice, &bad).is_err());
+}
+
+#[test]
+fn test_history_filters() {
+    let bc = busy();
+    let alice = address("alice");
+    let run = |query: HistoryQuery| amounts(&history::address_history(&bc, &alice, &query).unwrap().transactions);
+
+    assert_eq!(run(HistoryQuery { direction: Some(Direction::Sent), ..HistoryQuery::default() }), vec!["2", "1"]);
+    assert_eq!(run(HistoryQuery { direction: Some(Direction::Received), ..HistoryQuery::default() }).len(), 5);
+    assert_eq!(run(HistoryQuery { status: Some(StatusFilter::Pending), ..HistoryQuery::default() }), vec!["2"]);
+    assert_eq!(run(HistoryQuery { from_height: Some(2), to_height: Some(4), ..HistoryQuery::default() }), vec!["4", "3", "2"]);
+    assert_eq!(run(HistoryQuery { from_height: Some(5), order: Order::Asc, ..HistoryQuery::default() }), vec!["5", "1"]);
+    assert!(run(HistoryQuery { network: Some("btc".to_string()), ..HistoryQuery::default() }).is_empty());
+    assert_eq!(run(HistoryQuery { network: Some("sol".to_string()), ..HistoryQuery::default() }).len(), 7);
+
+    // Block 1 udah dalem: confirmed
+    let confirmed = run(HistoryQuery { status: Some(StatusFilter::Confirmed), ..HistoryQuery::default() });
+    assert_eq!(confirmed.len(), (bc.chain.len() as u64 - CONFIRMATION_DEPTH) as usize);
+
+    let genesis_time = bc.chain[0].timestamp;
+    let latest = bc.chain.last().unwrap().timestamp;
+    assert!(run(HistoryQuery { to_time: Some(genesis_time), ..HistoryQuery::default() }).is_empty());
+    assert_eq!(run(HistoryQuery { from_time: Some(latest), to_time: Some(latest), status: Some(StatusFilter::Included), ..HistoryQuery::default() }).first().map(String::as_str), Some("1"));
+}
@@ -0,0 +1,86 @@
+use blockchain::index::{ChainIndex, TxLocation};
+use blockchain::storage::{BlockStore, INDEX_FILE};
+use blockchain::chainspec::ChainSpec;
+use blockchain::{encoding, Blockchain};
+
+mod common;
+use common::{temp_data_dir, address, mine_on, sol, transfer};
+
+#[test]
+fn test_index_tracks_transactions_addresses_and_blocks() {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    let first = transfer("genesis", "alice", "1", 0);
+    let second = transfer("genesis", "alice", "2", 1);
+    bc.add_block(vec![first.clone()]);
+    bc.add_block(vec![second.clone(), transfer("genesis", "bob", "3", 2)]);
+
+    let location = bc.index.transaction(&second.txid).unwrap();
+    assert_eq!(location.block, 2);
+    assert_eq!(bc.chain[2].transactions[location.position].txid, second.txid);
+    assert_eq!(bc.index.address_transactions(&address("alice")), &[first.txid.clone(), second.txid.clone()]);
+    assert_eq!(bc.index.block_height(&bc.chain[1].hash), Some(1));
+    assert_eq!(bc.block_by_hash(&bc.chain[2].hash).unwrap().index, 2);
+
+    let history = bc.address_history(&address("alice"));
+    assert_eq!(history.iter().map(|tx| tx.amount).collect::<Vec<_>>(), vec![sol("1"), sol("2")]);
+    assert!(bc.address_history(&address("nobody")).is_empty());
+    assert_eq!(bc.index, ChainIndex::build(&bc.chain));
+}
+
+#[test]
+fn test_coinbase_txids_are_unique_per_block() {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    // Dua block di detik yang sama tetap dapet txid reward beda
+    bc.add_block(vec![transfer("genesis", "alice", "1", 0)]);
+    bc.add_block(vec![transfer("genesis", "alice", "1", 1)]);
+
+    let rewards = bc.address_history("miner_address");
+    assert_eq!(rewards.len(), 2);
+    assert_ne!(rewards[0].txid, rewards[1].txid);
+    assert_eq!(rewards[0].txid, encoding::transaction_id(&rewards[0]));
+    assert_eq!(bc.index.transaction(&rewards[1].txid).unwrap().block, 2);
+}
+
+#[test]
+fn test_index_follows_reorg() {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    let genesis = bc.chain[0].clone();
+    let dropped = transfer("genesis", "alice", "1", 0);
+    bc.add_block(vec![dropped.clone()]);
+
+    let rival = transfer("genesis", "bob", "5", 0);
+    let b1 = mine_on(&bc, &genesis, vec![rival.clone()]);
+    bc.accept_block(b1.clone()).unwrap();
+    let b2 = mine_on(&bc, &b1, vec![]);
+    bc.accept_block(b2.clone()).unwrap();
+    assert_eq!(bc.chain.last().unwrap().hash, b2.hash);
+
+    assert!(bc.index.transaction(&dropped.txid).is_none());
+    assert!(bc.index.address_transactions(&address("alice")).is_empty());
+    assert_eq!(bc.index.transaction(&rival.txid), Some(TxLocation { block: 1, position: 0 }));
+    assert_eq!(bc.index.block_height(&b2.hash), Some(2));
+    assert_eq!(bc.index.tip(), b2.hash);
+    assert_eq!(bc.index, ChainIndex::build(&bc.chain));
+}
+
+#[test]
+fn test_index_snapshot_is_restored_and_caught_up() {
+    let dir = temp_data_dir("index");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    bc.add_block(vec![transfer("genesis", "alice", "1", 0)]);
+    bc.add_block(vec![transfer("genesis", "alice", "2", 1)]);
+    let expected = bc.index.clone();
+    drop(bc);
+
+    // Snapshot terakhir masih di genesis, block sesudahnya dikejar dari block store
+    let store = BlockStore::open(&dir).unwrap();
+    assert_ne!(store.load_index().unwrap().tip(), expected.tip());
+    let reopened = Blockchain::open(&dir).unwrap();
+    assert_eq!(reopened.index, expected);
+    assert_eq!(store.load_index().unwrap(), expected);
+    drop(reopened);
+
+    // Snapshot rusak dibangun ulang dari chain
+    std::fs::write(dir.join(INDEX_FILE), "{not json").unwrap();
+    assert_eq!(Blockchain::open(&dir).unwrap().index, expected);
+}
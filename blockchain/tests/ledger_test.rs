@@ -0,0 +1,151 @@
+use blockchain::ledger::COINBASE_ADDRESS;
+use blockchain::miner::MinerState;
+use blockchain::status::TxStatus;
+use blockchain::validation::ValidationErrorKind;
+use blockchain::{ledger, merkle, mining, Amount, Block, Blockchain, Transaction};
+
+mod common;
+use common::{temp_data_dir, address, sol, transfer};
+
+// Coinbase buat block #1
+fn coinbase(to: &str, amount: &str) -> Transaction {
+    Transaction::new(COINBASE_ADDRESS.to_string(), address(to), sol(amount), format!("{} SOL", amount).parse().unwrap(), "SOL".to_string(), Amount::ZERO)
+        .with_nonce(1)
+}
+
+// Block di atas tip dengan transaksi apa adanya, tanpa reward tambahan dari `build_candidate`
+fn mine_raw(bc: &Blockchain, transactions: Vec<Transaction>) -> Block {
+    let tip = bc.chain.last().unwrap();
+    let block = Block {
+        index: tip.index + 1,
+        timestamp: chrono::Utc::now().timestamp() as u64,
+        merkle_root: merkle::merkle_root(&transactions),
+        transactions,
+        previous_hash: tip.hash.clone(),
+        hash: String::new(),
+        nonce: 0,
+        difficulty: bc.difficulty,
+    };
+    mining::mine_candidate(block, 1, &MinerState::default()).unwrap()
+}
+
+#[test]
+fn test_miner_reward_and_fees_come_from_block() {
+    let dir = temp_data_dir("reward");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    bc.add_block(vec![transfer("genesis", "alice", "10.0", 0)]);
+
+    assert_eq!(bc.wallet.get_balance(&address("genesis"), "SOL"), sol("89.89"));
+    assert_eq!(bc.wallet.get_balance(&address("alice"), "SOL"), sol("10"));
+    // Reward = subsidy 50 + setengah fee, dikredit sekali aja lewat coinbase
+    assert_eq!(bc.wallet.get_balance("miner_address", "SOL"), sol("50.055"));
+    assert_eq!(bc.wallet.get_balance(ledger::COINBASE_ADDRESS, "SOL"), Amount::ZERO);
+}
+
+#[test]
+fn test_overspend_is_dropped_before_mining() {
+    let dir = temp_data_dir("overspend");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    bc.add_block(vec![transfer("genesis", "alice", "60.0", 0), transfer("genesis", "bob", "60.0", 1)]);
+
+    let block = bc.chain.last().unwrap();
+    assert!(block.transactions.iter().all(|tx| tx.to != address("bob")));
+    assert_eq!(bc.wallet.get_balance(&address("bob"), "SOL"), Amount::ZERO);
+    assert_eq!(bc.wallet.get_balance(&address("genesis"), "SOL"), sol("39.89"));
+}
+
+#[test]
+fn test_replay_matches_live_state() {
+    let dir = temp_data_dir("replay");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    bc.add_block(vec![transfer("genesis", "alice", "10.0", 0)]);
+    bc.add_block(vec![transfer("alice", "bob", "4.0", 0)]);
+
+    let replayed = ledger::replay(&bc.chain, &bc.spec).unwrap();
+    for address in [address("genesis"), address("alice"), address("bob"), "miner_address".to_string()] {
+        assert_eq!(replayed.get_balance(&address, "SOL"), bc.wallet.get_balance(&address, "SOL"));
+    }
+
+    let reopened = Blockchain::open(&dir).unwrap();
+    assert_eq!(reopened.wallet.get_balance(&address("bob"), "SOL"), sol("4"));
+    assert_eq!(reopened.wallet.get_balance("miner_address", "SOL"), bc.wallet.get_balance("miner_address", "SOL"));
+}
+
+#[test]
+fn test_only_next_nonce_is_accepted() {
+    let dir = temp_data_dir("nonce");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    let genesis = address("genesis");
+    bc.add_block(vec![transfer("genesis", "alice", "1.0", 0), transfer("genesis", "alice", "2.0", 1)]);
+    assert_eq!(bc.wallet.get_nonce(&genesis), 2);
+    assert_eq!(bc.wallet.get_nonce(&address("alice")), 0);
+
+    // Replay nonce lama sama lompat nonce sama-sama ditolak
+    let mut wallet = bc.wallet.clone();
+    assert!(ledger::apply_transaction(&mut wallet, &transfer("genesis", "alice", "1.0", 1), &bc.spec).unwrap_err().to_string().contains("expected 2"));
+    assert!(ledger::apply_transaction(&mut wallet, &transfer("genesis", "alice", "1.0", 5), &bc.spec).is_err());
+    ledger::apply_transaction(&mut wallet, &transfer("genesis", "alice", "1.0", 2), &bc.spec).unwrap();
+    assert_eq!(wallet.get_nonce(&genesis), 3);
+
+    // Revert block balikin nonce juga
+    let block = bc.chain.last().unwrap().clone();
+    let mut reverted = bc.wallet.clone();
+    ledger::revert_block(&mut reverted, &block).unwrap();
+    assert_eq!(reverted.get_nonce(&genesis), 0);
+}
+
+#[test]
+fn test_coinbase_is_limited_to_one_capped_reward_after_user_transactions() {
+    let mut bc = Blockchain::in_memory(Default::default());
+    let fee_share_and_subsidy = "50.055";
+    let rejected = [
+        // Lebih dari subsidi + setengah fee
+        vec![transfer("genesis", "alice", "1.0", 0), coinbase("attacker", "1000000000")],
+        vec![transfer("genesis", "alice", "1.0", 0), coinbase("attacker", "50.056")],
+        // Dua mint identik di network yang sama
+        vec![coinbase("attacker", "1"), coinbase("attacker", "1")],
+        // Coinbase harus sesudah transaksi user
+        vec![coinbase("attacker", "1"), transfer("genesis", "alice", "1.0", 0)],
+        // Nonce coinbase harus height block
+        vec![coinbase("attacker", "1").with_nonce(0)],
+    ];
+    for transactions in rejected {
+        let block = mine_raw(&bc, transactions);
+        let err = bc.accept_block(block.clone()).unwrap_err();
+        assert!(err.contains("oinbase"), "{}", err);
+        assert_eq!(bc.chain.len(), 1);
+
+        // Validator full chain nolak block yang sama
+        let mut forged = bc.chain.clone();
+        forged.push(block);
+        let err = blockchain::validation::validate_blocks(&forged, &bc.spec).unwrap_err();
+        assert!(matches!(err.kind, ValidationErrorKind::InvalidCoinbase { .. }), "{:?}", err);
+    }
+    assert_eq!(bc.wallet.get_balance(&address("attacker"), "SOL"), Amount::ZERO);
+
+    let block = mine_raw(&bc, vec![transfer("genesis", "alice", "1.0", 0), coinbase("attacker", fee_share_and_subsidy)]);
+    bc.accept_block(block).unwrap();
+    assert_eq!(bc.wallet.get_balance(&address("attacker"), "SOL"), sol(fee_share_and_subsidy));
+    assert_eq!(bc.validate_chain(), Ok(()));
+}
+
+#[test]
+fn test_coinbase_cannot_reuse_a_pending_txid() {
+    let mut bc = Blockchain::in_memory(Default::default());
+    let pending = transfer("genesis", "alice", "1.0", 0);
+    bc.submit_transaction(pending.clone()).unwrap();
+
+    let mut fake = coinbase("attacker", "1");
+    fake.txid = pending.txid.clone();
+    let block = mine_raw(&bc, vec![fake]);
+    let err = bc.accept_block(block.clone()).unwrap_err();
+    assert!(err.contains("txid of its payload"), "{}", err);
+    let mut forged = bc.chain.clone();
+    forged.push(block);
+    let err = blockchain::validation::validate_blocks(&forged, &bc.spec).unwrap_err();
+    assert!(matches!(err.kind, ValidationErrorKind::InvalidCoinbase { .. }), "{:?}", err);
+
+    // Transaksi aslinya masih nunggu di mempool
+    assert!(bc.mempool.contains(&pending.txid));
+    assert_eq!(bc.transaction_status(&pending.txid), Some(TxStatus::Pending));
+}
@@ -0,0 +1,116 @@
+use blockchain::chainspec::ChainSpec;
+use blockchain::ledger::COINBASE_ADDRESS;
+use blockchain::mempool::Mempool;
+use blockchain::status::TxStatus;
+use blockchain::{Amount, Blockchain, Transaction, TxError, Wallet};
+
+mod common;
+use common::{temp_data_dir, address, sol, transfer_with_fee};
+
+fn funded_wallet(amount: &str) -> Wallet {
+    let mut wallet = Wallet::new();
+    wallet.credit(&address("alice"), "SOL", sol(amount)).unwrap();
+    wallet.credit(&address("bob"), "SOL", sol(amount)).unwrap();
+    wallet
+}
+
+#[test]
+fn test_submit_is_pending_until_block_is_produced() {
+    let dir = temp_data_dir("pending");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    let tx = transfer_with_fee("genesis", "alice", "10.0", "0.11", 0);
+    bc.submit_transaction(tx.clone()).unwrap();
+    assert_eq!(bc.chain.len(), 1);
+    assert_eq!(bc.mempool.get(&tx.txid).unwrap().status, TxStatus::Pending);
+
+    assert_eq!(bc.produce_block(), Some(1));
+    assert!(bc.mempool.is_empty());
+    assert_eq!(bc.wallet.get_balance(&address("alice"), "SOL"), sol("10"));
+    assert!(bc.submit_transaction(tx).is_err(), "already in chain");
+    assert_eq!(bc.produce_block(), None);
+}
+
+#[test]
+fn test_duplicates_and_pending_overspend_are_rejected() {
+    let wallet = funded_wallet("10.0");
+    let spec = ChainSpec::default();
+    let mut mempool = Mempool::default();
+    let first = transfer_with_fee("alice", "bob", "6.0", "0.2", 0);
+    mempool.insert(first.clone(), &wallet, &spec, 100).unwrap();
+    assert!(mempool.insert(first, &wallet, &spec, 100).is_err());
+    // Saldo 10 udah kepake 6.2 di mempool
+    assert!(mempool.insert(transfer_with_fee("alice", "carol", "6.0", "0.2", 1), &wallet, &spec, 100).is_err());
+    assert_eq!(mempool.available_balance(&wallet, &address("alice"), "SOL"), sol("3.8"));
+}
+
+#[test]
+fn test_coinbase_cannot_be_submitted() {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    let mint = Transaction::new(COINBASE_ADDRESS.to_string(), address("mallory"), sol("5"), "5 SOL".parse().unwrap(), "SOL".to_string(), Amount::ZERO);
+    assert!(bc.submit_transaction(mint.clone()).unwrap_err().to_string().contains("cannot be submitted"));
+    let err = Mempool::default().insert(mint, &bc.wallet, &bc.spec, 100).unwrap_err().to_string();
+    assert!(err.contains("cannot be submitted"), "{}", err);
+    assert!(bc.mempool.is_empty());
+}
+
+#[test]
+fn test_nonce_must_follow_pending_transactions() {
+    let wallet = funded_wallet("10.0");
+    let spec = ChainSpec::default();
+    let mut mempool = Mempool::default();
+    assert_eq!(mempool.next_nonce(&wallet, &address("alice")), 0);
+    mempool.insert(transfer_with_fee("alice", "bob", "1.0", "0.2", 0), &wallet, &spec, 100).unwrap();
+    assert_eq!(mempool.next_nonce(&wallet, &address("alice")), 1);
+
+    let err = mempool.insert(transfer_with_fee("alice", "carol", "1.0", "0.2", 0), &wallet, &spec, 100).unwrap_err();
+    assert!(matches!(&err, TxError::Conflict(reason) if reason.contains("already pending")), "{:?}", err);
+    let err = mempool.insert(transfer_with_fee("alice", "carol", "1.0", "0.2", 5), &wallet, &spec, 100).unwrap_err().to_string();
+    assert!(err.contains("skips ahead"), "{}", err);
+    mempool.insert(transfer_with_fee("alice", "carol", "1.0", "0.2", 1), &wallet, &spec, 100).unwrap();
+
+    // Nonce 0 udah kepake di chain: yang pending jadi basi
+    let mut confirmed = wallet.clone();
+    confirmed.set_nonce(&address("alice"), 1);
+    let err = mempool.insert(transfer_with_fee("alice", "dave", "1.0", "0.2", 0), &confirmed, &spec, 100).unwrap_err().to_string();
+    assert!(err.contains("already used"), "{}", err);
+    assert_eq!(mempool.prune_stale(&confirmed).len(), 1);
+    assert_eq!(mempool.len(), 1);
+}
+
+#[test]
+fn test_selection_orders_by_fee_and_respects_dependencies() {
+    let wallet = funded_wallet("100.0");
+    let spec = ChainSpec::default();
+    let mut mempool = Mempool::default();
+    let cheap = transfer_with_fee("alice", "carol", "1.0", "0.11", 0);
+    let pricey = transfer_with_fee("bob", "carol", "1.0", "0.5", 0);
+    // Fee paling gede tapi harus nunggu nonce 0 dari alice
+    let next = transfer_with_fee("alice", "carol", "1.0", "0.9", 1);
+    mempool.insert(cheap.clone(), &wallet, &spec, 100).unwrap();
+    mempool.insert(pricey.clone(), &wallet, &spec, 101).unwrap();
+    mempool.insert(next.clone(), &wallet, &spec, 102).unwrap();
+
+    let selected: Vec<String> = mempool.select(&wallet, &spec, 1, 100, 10).into_iter().map(|tx| tx.txid).collect();
+    assert_eq!(selected, vec![pricey.txid.clone(), cheap.txid, next.txid]);
+
+    let limited = mempool.select(&wallet, &spec, 1, 100, 1);
+    assert_eq!(limited.len(), 1);
+    assert_eq!(limited[0].txid, pricey.txid);
+}
+
+#[test]
+fn test_full_mempool_evicts_lowest_fee_and_expires_old() {
+    let wallet = funded_wallet("100.0");
+    let spec = ChainSpec::default();
+    let mut mempool = Mempool::new(60, 2);
+    let low = transfer_with_fee("alice", "bob", "1.0", "0.11", 0);
+    mempool.insert(low.clone(), &wallet, &spec, 100).unwrap();
+    mempool.insert(transfer_with_fee("alice", "bob", "1.0", "0.15", 1), &wallet, &spec, 130).unwrap();
+    assert!(mempool.insert(transfer_with_fee("alice", "bob", "1.0", "0.11", 2), &wallet, &spec, 130).is_err());
+    mempool.insert(transfer_with_fee("alice", "bob", "1.0", "0.3", 2), &wallet, &spec, 130).unwrap();
+    assert!(!mempool.contains(&low.txid));
+
+    assert!(mempool.expire(150, 1).is_empty());
+    assert_eq!(mempool.expire(200, 1).len(), 2);
+    assert!(mempool.is_empty());
+}
@@ -0,0 +1,65 @@
+use blockchain::merkle::{self, EMPTY_ROOT};
+use blockchain::{chainspec, Amount, Blockchain, Transaction};
+
+mod common;
+use common::temp_data_dir;
+
+fn transfers(count: usize) -> Vec<Transaction> {
+    (0..count)
+        .map(|i| {
+            let mut tx = Transaction::new(chainspec::dev_genesis_address(), format!("user{}", i), Amount::from_units(1_000_000_000), "15000 IDR".parse().unwrap(), "SOL".to_string(), Amount::from_units(110_000_000))
+                .with_nonce(i as u64);
+            tx.sign(&chainspec::dev_genesis_keypair());
+            tx
+        })
+        .collect()
+}
+
+#[test]
+fn test_proof_for_every_position() {
+    // Ganjil sama genap, biar node tanpa pasangan ikut kecek
+    for count in [1, 2, 5, 8] {
+        let txs = transfers(count);
+        let root = merkle::merkle_root(&txs);
+        for position in 0..count {
+            let proof = merkle::build_proof(&txs, position).unwrap();
+            assert_eq!(merkle::proof_root(&proof).unwrap(), root, "count {} position {}", count, position);
+        }
+    }
+    assert_eq!(merkle::merkle_root(&[]), EMPTY_ROOT);
+}
+
+#[test]
+fn test_light_client_verifies_inclusion() {
+    let dir = temp_data_dir("merkle");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    let txs = transfers(3);
+    bc.add_block(txs.clone());
+
+    let (header, proof) = bc.transaction_proof(&txs[1].txid).unwrap();
+    assert_eq!(header.index, 1);
+    assert!(merkle::verify_proof(&proof, &header));
+    let included = bc.chain[1].transactions.iter().find(|tx| tx.txid == txs[1].txid).unwrap();
+    assert!(merkle::verify_inclusion(included, &proof, &header));
+    assert!(!merkle::verify_inclusion(&txs[0], &proof, &header));
+}
+
+#[test]
+fn test_forged_proof_or_header_is_rejected() {
+    let dir = temp_data_dir("forged");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    let txs = transfers(4);
+    bc.add_block(txs.clone());
+    let (header, proof) = bc.transaction_proof(&txs[2].txid).unwrap();
+
+    let mut forged_proof = proof.clone();
+    forged_proof.tx_hash = merkle::transaction_hash(&transfers(1)[0]);
+    assert!(!merkle::verify_proof(&forged_proof, &header));
+
+    // Header dengan merkle_root palsu ketahuan karena hash header-nya ga cocok lagi
+    let mut forged_header = header.clone();
+    forged_header.merkle_root = merkle::proof_root(&forged_proof).unwrap();
+    assert!(!merkle::verify_proof(&forged_proof, &forged_header));
+
+    assert!(bc.transaction_proof("missing").is_none());
+}
@@ -0,0 +1,143 @@
+use blockchain::chainspec::ChainSpec;
+use blockchain::miner::{spawn_miner, MinerConfig, MinerState};
+use blockchain::mining::{MiningStats, SearchOutcome, MINING_STATS_FILE};
+use blockchain::{encoding, mining, validation, Blockchain, Wallet};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+mod common;
+use common::{address, sol, temp_data_dir, transfer};
+
+#[test]
+fn test_abort_stops_mining_attempt() {
+    // Difficulty segede ini praktis ga mungkin ketemu, jadi cuma bisa berhenti lewat abort
+    let candidate = mining::build_candidate(1, "0".repeat(64), 0, vec![], u64::MAX, &ChainSpec::default(), &Wallet::new());
+    let state = Arc::new(MinerState::default());
+    let aborter = {
+        let state = state.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            state.request_abort();
+        })
+    };
+    assert!(mining::mine_candidate(candidate, 2, &state).is_none());
+    aborter.join().unwrap();
+
+    let status = state.status();
+    assert!(!status.running);
+    assert_eq!(status.workers, 2);
+    assert!(status.hashes > 0);
+    assert_eq!(status.attempts_aborted, 1);
+}
+
+#[test]
+fn test_nonce_strides_cover_range_exactly_once() {
+    let candidate = mining::build_candidate(1, "0".repeat(64), 0, vec![], u64::MAX, &ChainSpec::default(), &Wallet::new());
+    let prefix = encoding::header_prefix(&candidate.header());
+    let state = MinerState::default();
+    state.begin(1, 4);
+    assert_eq!(mining::search_nonces(&prefix, u64::MAX, 4, 9_999, &state), SearchOutcome::Exhausted);
+
+    // 10_000 nonce dibagi rata ke 4 thread, ga ada yang dobel
+    let status = state.status();
+    assert_eq!(status.hashes, 10_000);
+    assert_eq!(status.thread_hashes, vec![2_500; 4]);
+}
+
+#[test]
+fn test_mined_block_reports_hashes_and_valid_nonce() {
+    let candidate = mining::build_candidate(1, "0".repeat(64), 0, vec![], 1 << 12, &ChainSpec::default(), &Wallet::new());
+    let state = MinerState::default();
+    let block = mining::mine_candidate(candidate, 3, &state).unwrap();
+    assert_eq!(encoding::block_hash(&block), block.hash);
+    assert!(blockchain::difficulty::meets_target(&block.hash, block.difficulty));
+
+    let status = state.status();
+    assert_eq!(status.blocks_mined, 1);
+    assert_eq!(status.thread_hashes.len(), 3);
+    assert_eq!(status.thread_hashes.iter().sum::<u64>(), status.hashes);
+    assert!(status.hashes > 0);
+}
+
+#[test]
+fn test_new_transaction_and_new_tip_abort_current_template() {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    assert!(bc.block_template().is_none());
+    assert!(!bc.miner.abort_requested());
+
+    bc.submit_transaction(transfer("genesis", "alice", "10.0", 0)).unwrap();
+    assert!(bc.miner.abort_requested());
+
+    let template = bc.block_template().unwrap();
+    assert!(!bc.miner.abort_requested());
+    assert_eq!(template.index, 1);
+    assert_eq!(template.previous_hash, bc.chain[0].hash);
+
+    let block = mining::mine_candidate(template, 2, &MinerState::default()).unwrap();
+    bc.accept_block(block).unwrap();
+    assert!(bc.miner.abort_requested());
+}
+
+#[test]
+fn test_background_miner_does_not_hold_lock() {
+    let bc = Arc::new(Mutex::new(Blockchain::in_memory(ChainSpec::default())));
+    let config = MinerConfig { workers: 2, poll_interval: Duration::from_millis(50) };
+    spawn_miner(bc.clone(), config);
+    bc.lock().unwrap().submit_transaction(transfer("genesis", "alice", "10.0", 0)).unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(30);
+    loop {
+        {
+            let bc = bc.lock().unwrap();
+            if bc.chain.len() == 2 {
+                assert_eq!(bc.wallet.get_balance(&address("alice"), "SOL"), sol("10"));
+                assert!(bc.mempool.is_empty());
+                assert!(bc.miner.status().enabled);
+                assert!(bc.miner.status().blocks_mined >= 1);
+                break;
+            }
+        }
+        assert!(Instant::now() < deadline, "background miner did not produce a block");
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+#[test]
+fn test_mining_stats_are_written_to_the_data_dir_by_the_background_miner() {
+    let dir = temp_data_dir("mining-stats");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    // Mining lewat library ga nulis file apa pun
+    bc.add_block(vec![transfer("genesis", "alice", "1.0", 0)]);
+    assert!(!dir.join(MINING_STATS_FILE).exists());
+
+    let bc = Arc::new(Mutex::new(bc));
+    spawn_miner(bc.clone(), MinerConfig { workers: 2, poll_interval: Duration::from_millis(50) });
+    bc.lock().unwrap().submit_transaction(transfer("genesis", "alice", "1.0", 1)).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while bc.lock().unwrap().chain.len() < 3 {
+        assert!(Instant::now() < deadline, "background miner did not produce a block");
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    let stats: Vec<MiningStats> = serde_json::from_str(&std::fs::read_to_string(dir.join(MINING_STATS_FILE)).unwrap()).unwrap();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].block_index, 2);
+    assert_eq!(stats[0].hash, bc.lock().unwrap().chain[2].hash);
+}
+
+#[test]
+fn test_candidate_is_never_stamped_before_a_future_parent() {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    // Block peer yang jam-nya lebih cepat, masih di dalam batas drift
+    let ahead = chrono::Utc::now().timestamp() as u64 + validation::max_future_drift(&bc.spec) / 2;
+    let genesis = bc.chain[0].clone();
+    let mut peer = mining::build_candidate(1, genesis.hash.clone(), genesis.timestamp, vec![], bc.difficulty, &bc.spec, &bc.wallet);
+    peer.timestamp = ahead;
+    bc.accept_block(mining::mine_candidate(peer, 1, &MinerState::default()).unwrap()).unwrap();
+
+    bc.submit_transaction(transfer("genesis", "alice", "1.0", 0)).unwrap();
+    assert_eq!(bc.produce_block(), Some(2));
+    assert!(bc.chain[2].timestamp >= ahead);
+    assert_eq!(bc.validate_chain(), Ok(()));
+}
@@ -0,0 +1,114 @@
+use blockchain::chainspec::{self, ChainSpec};
+use blockchain::multisig::{self, MultisigPolicy};
+use blockchain::{encoding, Blockchain, PartialSignature, Transaction};
+use wallet::encryption::{self};
+
+mod common;
+use common::{key, address, sol};
+
+fn treasury() -> MultisigPolicy {
+    MultisigPolicy::new(2, vec![address("carol"), address("alice"), address("bob")]).unwrap()
+}
+
+// Blockchain dengan 10 SOL di address treasury
+fn funded() -> Blockchain {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    let mut fund = Transaction::new(chainspec::dev_genesis_address(), treasury().address(), sol("10"), "10 SOL".parse().unwrap(), "SOL".to_string(), sol("0.11"));
+    fund.sign(&chainspec::dev_genesis_keypair());
+    bc.add_block(vec![fund]);
+    bc
+}
+
+fn spend(nonce: u64) -> Transaction {
+    Transaction::new(treasury().address(), address("dave"), sol("1"), "1 SOL".parse().unwrap(), "SOL".to_string(), sol("0.11"))
+        .with_nonce(nonce)
+        .with_multisig(treasury())
+}
+
+#[test]
+fn test_multisig_address_derivation() {
+    let policy = treasury();
+    let reordered = MultisigPolicy::new(2, vec![address("bob"), address("alice"), address("carol")]).unwrap();
+    assert_eq!(policy.address(), reordered.address());
+    let stricter = MultisigPolicy::new(3, policy.signers.clone()).unwrap();
+    assert_ne!(policy.address(), stricter.address());
+
+    assert!(multisig::is_multisig_address(&policy.address()));
+    assert!(!multisig::is_multisig_address(&address("alice")));
+    assert!(encryption::public_key_from_address(&policy.address()).is_err());
+
+    assert!(MultisigPolicy::new(0, vec![address("alice")]).is_err());
+    assert!(MultisigPolicy::new(2, vec![address("alice")]).is_err());
+    assert!(MultisigPolicy::new(1, vec![address("alice"), address("alice")]).is_err());
+    assert!(MultisigPolicy::new(1, vec!["not-a-key".to_string()]).is_err());
+}
+
+#[test]
+fn test_threshold_is_enforced() {
+    let mut bc = funded();
+    let treasury_address = treasury().address();
+
+    let mut tx = spend(0);
+    tx.sign_multisig(&key("alice")).unwrap();
+    let err = bc.submit_transaction(tx.clone()).unwrap_err().to_string();
+    assert!(err.contains("needs 2 of 3 signatures, got 1"), "{}", err);
+    // Orang luar ga bisa ikut tanda tangan
+    assert!(tx.sign_multisig(&key("mallory")).is_err());
+
+    // Signature biasa dari salah satu signer ga cukup
+    let mut single = spend(0);
+    single.multisig = None;
+    single.sign(&key("alice"));
+    assert!(bc.submit_transaction(single).unwrap_err().to_string().contains("has no multisig signatures"));
+
+    // Jumlah diubah setelah tanda tangan: signature-nya ga cocok lagi
+    tx.sign_multisig(&key("carol")).unwrap();
+    let mut tampered = tx.clone();
+    tampered.amount = sol("5");
+    tampered.txid = encoding::transaction_id(&tampered);
+    assert!(bc.submit_transaction(tampered).is_err());
+
+    // Signature dobel dari signer yang sama ga dihitung dua kali
+    let mut doubled = spend(0);
+    doubled.sign_multisig(&key("alice")).unwrap();
+    let witness = doubled.multisig.as_mut().unwrap();
+    witness.signatures.push(witness.signatures[0].clone());
+    assert!(bc.submit_transaction(doubled).unwrap_err().to_string().contains("Duplicate signature"));
+
+    bc.submit_transaction(tx).unwrap();
+    assert_eq!(bc.produce_block(), Some(2));
+    assert_eq!(bc.wallet.get_balance(&address("dave"), "SOL"), sol("1"));
+    assert_eq!(bc.wallet.get_balance(&treasury_address, "SOL"), sol("8.89"));
+    assert_eq!(bc.validate_chain(), Ok(()));
+}
+
+#[test]
+fn test_partial_signatures_are_collected_before_broadcast() {
+    let mut bc = funded();
+    let status = bc.propose_multisig(spend(0)).unwrap();
+    assert!(!status.complete && !status.submitted);
+    assert_eq!(status.missing.len(), 3);
+    let txid = status.txid.clone();
+    assert!(bc.propose_multisig(spend(0)).is_err());
+    assert!(bc.mempool.is_empty());
+
+    let payload = encoding::transaction_payload(bc.signing.get(&txid).unwrap());
+    let forged = PartialSignature { signer: address("bob"), signature: encryption::sign(&key("mallory"), &payload) };
+    assert!(bc.sign_multisig(&txid, forged).is_err());
+
+    let bob = PartialSignature { signer: address("bob"), signature: encryption::sign(&key("bob"), &payload) };
+    let status = bc.sign_multisig(&txid, bob.clone()).unwrap();
+    assert_eq!(status.signed, vec![address("bob")]);
+    // Tanda tangan ulang dari signer yang sama cuma ngeganti
+    let status = bc.sign_multisig(&txid, bob).unwrap();
+    assert!(!status.submitted);
+    assert_eq!(status.signed.len(), 1);
+
+    let carol = PartialSignature { signer: address("carol"), signature: encryption::sign(&key("carol"), &payload) };
+    let status = bc.sign_multisig(&txid, carol).unwrap();
+    assert!(status.complete && status.submitted);
+    assert!(bc.signing.is_empty());
+    assert_eq!(bc.mempool.transactions()[0].txid, txid);
+    assert_eq!(bc.produce_block(), Some(2));
+    assert_eq!(bc.wallet.get_balance(&address("dave"), "SOL"), sol("1"));
+}
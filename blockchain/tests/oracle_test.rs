@@ -0,0 +1,98 @@
+use blockchain::chainspec::{self, ChainSpec};
+use blockchain::oracle::{PriceFeed, PriceOracle};
+use blockchain::{Amount, Blockchain, Peg, Transaction};
+use std::collections::BTreeMap;
+
+mod common;
+use common::sol;
+
+const NOW: u64 = 1_700_000_000;
+
+fn feed(updated_at: u64, quotes: &[(&str, &str, &str)]) -> PriceFeed {
+    let mut prices: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+    for (asset, currency, price) in quotes {
+        prices.entry(asset.to_string()).or_default().insert(currency.to_string(), price.to_string());
+    }
+    PriceFeed { updated_at, prices }
+}
+
+fn transfer(amount: &str, peg_value: &str, nonce: u64) -> Transaction {
+    let mut tx = Transaction::new(chainspec::dev_genesis_address(), "alice".to_string(), sol(amount), peg_value.parse().unwrap(), "SOL".to_string(), sol("0.11"))
+        .with_nonce(nonce);
+    tx.sign(&chainspec::dev_genesis_keypair());
+    tx
+}
+
+#[test]
+fn test_feed_ingest_and_valuation() {
+    let mut oracle = PriceOracle::default();
+    assert_eq!(oracle.ingest(feed(NOW, &[("sol", "usd", "150.25"), ("SOL", "IDR", "2400000")]), NOW), Ok(2));
+    let value = oracle.value(sol("2.5"), 9, "SOL", "USD", NOW).unwrap().unwrap();
+    assert_eq!(value.to_decimal_string(8), "375.625");
+
+    let valuations = oracle.valuations(sol("2"), 9, "SOL", NOW);
+    assert_eq!(valuations.get("IDR").map(String::as_str), Some("4800000"));
+    assert_eq!(valuations.get("USD").map(String::as_str), Some("300.5"));
+    assert_eq!(oracle.feed().prices["SOL"]["USD"], "150.25");
+
+    // Harga basi ga dipakai, kecuali peg ke simbol sendiri
+    let later = NOW + oracle.max_age_secs + 1;
+    assert_eq!(oracle.value(sol("1"), 9, "SOL", "USD", later), Ok(None));
+    assert!(oracle.valuations(sol("1"), 9, "SOL", later).is_empty());
+    assert_eq!(oracle.value(sol("1"), 9, "SOL", "SOL", later).unwrap().unwrap().to_decimal_string(8), "1");
+
+    // Feed rusak ditolak utuh, harga lama tetap
+    assert!(oracle.ingest(feed(NOW, &[("SOL", "USD", "1"), ("ETH", "USD", "abc")]), NOW).is_err());
+    assert_eq!(oracle.feed().prices["SOL"]["USD"], "150.25");
+
+    // Feed tanpa timestamp dianggap baru
+    oracle.ingest(feed(0, &[("SOL", "USD", "1")]), NOW + 10).unwrap();
+    assert_eq!(oracle.updated_at, NOW + 10);
+}
+
+#[test]
+fn test_feed_file_round_trip() {
+    let path = std::env::temp_dir().join(format!("prices-{}.json", uuid::Uuid::new_v4()));
+    std::fs::write(&path, r#"{"updated_at": 1700000000, "prices": {"ETH": {"USD": "2500"}}}"#).unwrap();
+    let mut oracle = PriceOracle::default();
+    oracle.ingest(PriceFeed::load(&path).unwrap(), NOW).unwrap();
+    // 0.5 ETH dalam wei, 18 desimal
+    let half = Amount::parse_decimal("0.5", 18).unwrap();
+    assert_eq!(oracle.value(half, 18, "ETH", "USD", NOW).unwrap().unwrap().to_decimal_string(8), "1250");
+
+    std::fs::write(&path, "not json").unwrap();
+    assert!(PriceFeed::load(&path).unwrap_err().contains("Invalid price feed"));
+}
+
+#[test]
+fn test_peg_is_checked_against_oracle_on_submit() {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    // Belum ada harga: peg ga bisa dicek, jadi diterima
+    bc.submit_transaction(transfer("1", "15000 IDR", 0)).unwrap();
+
+    bc.oracle.ingest(feed(0, &[("SOL", "IDR", "2400000")]), chrono::Utc::now().timestamp() as u64).unwrap();
+    let err = bc.submit_transaction(transfer("1", "15000 IDR", 1)).unwrap_err().to_string();
+    assert!(err.contains("away from the oracle price"), "{}", err);
+    assert!(err.contains("1 SOL is worth 2400000 IDR"), "{}", err);
+    // Dalam toleransi 5%
+    bc.submit_transaction(transfer("1", "2500000 IDR", 1)).unwrap();
+    assert!(bc.submit_transaction(transfer("1", "2600000 IDR", 2)).is_err());
+    // Peg ke simbol sendiri harus pas jumlahnya
+    assert!(bc.submit_transaction(transfer("1", "1.1 SOL", 2)).is_err());
+    bc.submit_transaction(transfer("1", "1 SOL", 2)).unwrap();
+
+    // Oracle bukan aturan konsensus: block tetap valid walau peg-nya jauh dari harga
+    assert_eq!(bc.produce_block(), Some(1));
+    assert_eq!(bc.chain[1].transactions.len(), 4);
+    assert_eq!(bc.validate_chain(), Ok(()));
+}
+
+#[test]
+fn test_native_peg_keeps_full_precision() {
+    let spec = ChainSpec::default();
+    let one_wei = spec.assets.native_peg(Amount::from_units(1), "ETH");
+    assert_eq!(one_wei.to_string(), "0.000000000000000001 ETH");
+    assert_eq!(one_wei.to_string().parse::<Peg>(), Ok(one_wei));
+    assert_eq!(spec.assets.native_peg(sol("50.5"), "SOL").to_string(), "50.5 SOL");
+    assert_eq!(spec.genesis_block().transactions[0].peg_value.to_string(), "100 SOL");
+}
@@ -0,0 +1,73 @@
+use blockchain::chainspec::{self, ChainSpec};
+use blockchain::ledger::COINBASE_ADDRESS;
+use blockchain::{encoding, Amount, Blockchain, Transaction};
+use wallet::encryption;
+
+mod common;
+use common::sol;
+
+fn unsigned_transfer(amount: &str) -> Transaction {
+    Transaction::new(chainspec::dev_genesis_address(), "alice".to_string(), sol(amount), "15000 IDR".parse().unwrap(), "SOL".to_string(), sol("0.11"))
+}
+
+#[test]
+fn test_signed_transfer_is_accepted() {
+    let mut tx = unsigned_transfer("1.0");
+    tx.sign(&chainspec::dev_genesis_keypair());
+    assert_eq!(tx.txid, encoding::transaction_id(&tx));
+    assert_eq!(tx.validate(), Ok(()));
+
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    bc.submit_transaction(tx).unwrap();
+    assert_eq!(bc.produce_block(), Some(1));
+    assert_eq!(bc.wallet.get_balance("alice", "SOL"), sol("1"));
+}
+
+#[test]
+fn test_unsigned_or_foreign_signature_is_rejected() {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    let err = bc.submit_transaction(unsigned_transfer("1.0")).unwrap_err().to_string();
+    assert!(err.contains("is not signed"), "{}", err);
+
+    // Ditandatangani kunci lain, bukan pemilik `from`
+    let mut forged = unsigned_transfer("1.0");
+    forged.sign(&encryption::generate_keypair());
+    let err = bc.submit_transaction(forged).unwrap_err().to_string();
+    assert!(err.contains("does not match sender"), "{}", err);
+
+    let mut garbage = unsigned_transfer("1.0");
+    garbage.signature = Some("not-base58-0OIl".to_string());
+    assert!(bc.submit_transaction(garbage).is_err());
+    assert!(bc.mempool.is_empty());
+}
+
+#[test]
+fn test_tampering_after_signing_is_rejected() {
+    let key = chainspec::dev_genesis_keypair();
+    let mut tx = unsigned_transfer("1.0");
+    tx.sign(&key);
+
+    let mut bigger = tx.clone();
+    bigger.amount = sol("90");
+    assert!(bigger.validate().is_err());
+
+    let mut redirected = tx.clone();
+    redirected.to = "mallory".to_string();
+    assert!(redirected.validate().is_err());
+
+    // Txid baru buat transaksi yang sama = replay, harus ditolak
+    let mut replay = tx.clone();
+    replay.txid = "replayed".to_string();
+    assert!(replay.validate().unwrap_err().to_string().contains("does not match its signed payload"));
+
+    // Address yang bukan public key ed25519
+    let mut bad_sender = tx;
+    bad_sender.from = "genesis".to_string();
+    assert!(bad_sender.validate().is_err());
+}
+
+#[test]
+fn test_coinbase_mint_needs_no_signature() {
+    let mint = Transaction::new(COINBASE_ADDRESS.to_string(), "alice".to_string(), sol("5"), "5 SOL".parse().unwrap(), "SOL".to_string(), Amount::ZERO);
+    assert_eq!(mint.validate(), Ok(()));
+}
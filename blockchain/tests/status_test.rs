@@ -0,0 +1,81 @@
+use blockchain::chainspec::ChainSpec;
+use blockchain::status::{TxLog, TxStatus, CONFIRMATION_DEPTH};
+use blockchain::{Blockchain, Transaction};
+
+mod common;
+use common::{address, transfer};
+
+#[test]
+fn test_status_follows_transaction_through_the_chain() {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    let tx = transfer("genesis", "alice", "1", 0);
+    assert_eq!(bc.transaction_status(&tx.txid), None);
+    bc.submit_transaction(tx.clone()).unwrap();
+    assert_eq!(bc.transaction_status(&tx.txid), Some(TxStatus::Pending));
+
+    assert_eq!(bc.produce_block(), Some(1));
+    assert_eq!(bc.transaction_status(&tx.txid), Some(TxStatus::Included { block: 1 }));
+    assert_eq!(bc.address_history(&address("alice"))[0].status, TxStatus::Included { block: 1 });
+
+    for _ in 1..CONFIRMATION_DEPTH {
+        bc.add_block(vec![]);
+    }
+    let found = bc.find_transaction(&tx.txid).unwrap();
+    assert_eq!(found.status, TxStatus::Confirmed { depth: CONFIRMATION_DEPTH });
+    assert_eq!(serde_json::to_value(&found.status).unwrap(), serde_json::json!({"state": "confirmed", "depth": 6}));
+}
+
+#[test]
+fn test_rejected_submissions_are_recorded_with_reason() {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    let broke = transfer("alice", "bob", "1", 0);
+    let err = bc.submit_transaction(broke.clone()).unwrap_err().to_string();
+    let found = bc.find_transaction(&broke.txid).unwrap();
+    assert_eq!(found.status, TxStatus::Rejected { reason: err.clone() });
+    assert!(err.contains("Insufficient balance"), "{}", err);
+
+    // Duplikat ga nimpa status transaksi aslinya
+    let funding = transfer("genesis", "alice", "5", 0);
+    bc.submit_transaction(funding.clone()).unwrap();
+    assert!(bc.submit_transaction(funding.clone()).is_err());
+    assert_eq!(bc.transaction_status(&funding.txid), Some(TxStatus::Pending));
+
+    // Setelah dapat saldo, transaksi yang sama boleh dicoba lagi
+    assert_eq!(bc.produce_block(), Some(1));
+    bc.submit_transaction(broke.clone()).unwrap();
+    assert_eq!(bc.transaction_status(&broke.txid), Some(TxStatus::Pending));
+    assert!(bc.tx_log.is_empty());
+}
+
+#[test]
+fn test_expired_and_superseded_transactions_are_recorded() {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    let old = transfer("genesis", "alice", "1", 0);
+    // Masuk mempool jauh di masa lalu, langsung kadaluarsa di putaran block berikutnya
+    bc.mempool.insert(old.clone(), &bc.wallet, &bc.spec, 1).unwrap();
+    assert_eq!(bc.produce_block(), None);
+    assert_eq!(bc.transaction_status(&old.txid), Some(TxStatus::Expired));
+
+    // Nonce yang sama keburu dipakai transaksi lain di block
+    let pending = transfer("genesis", "alice", "1", 0);
+    bc.submit_transaction(pending.clone()).unwrap();
+    bc.add_block(vec![transfer("genesis", "bob", "2", 0)]);
+    match bc.transaction_status(&pending.txid) {
+        Some(TxStatus::Rejected { reason }) => assert!(reason.contains("already used on chain"), "{}", reason),
+        other => panic!("unexpected status {:?}", other),
+    }
+}
+
+#[test]
+fn test_log_keeps_only_the_newest_entries() {
+    let mut log = TxLog::new(2);
+    let txs: Vec<Transaction> = (0..3).map(|i| transfer("genesis", "alice", "1", i)).collect();
+    for tx in &txs {
+        log.record(tx.clone(), TxStatus::Expired);
+    }
+    assert_eq!(log.len(), 2);
+    assert!(log.get(&txs[0].txid).is_none());
+    assert_eq!(log.get(&txs[2].txid).unwrap().status, TxStatus::Expired);
+    log.record(txs[1].clone(), TxStatus::Rejected { reason: "x".to_string() });
+    assert_eq!(log.len(), 2);
+}
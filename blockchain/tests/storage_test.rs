@@ -0,0 +1,100 @@
+use blockchain::storage::{BlockStore, BLOCKS_FILE};
+use blockchain::Blockchain;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+mod common;
+use common::{temp_data_dir, transfer};
+
+#[test]
+fn test_chain_survives_restart() {
+    let dir = temp_data_dir("restart");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    bc.add_block(vec![transfer("genesis", "bob", "1.0", 0)]);
+    bc.add_block(vec![transfer("genesis", "bob", "2.0", 1)]);
+    let tip = bc.chain.last().unwrap().hash.clone();
+    let index = bc.index.clone();
+    drop(bc);
+
+    let reopened = Blockchain::open(&dir).unwrap();
+    assert_eq!(reopened.chain.len(), 3);
+    assert_eq!(reopened.chain.last().unwrap().hash, tip);
+    assert_eq!(reopened.index, index);
+}
+
+#[test]
+fn test_corrupted_tail_is_discarded() {
+    let dir = temp_data_dir("corrupt");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    bc.add_block(vec![transfer("genesis", "bob", "1.0", 0)]);
+    bc.add_block(vec![transfer("genesis", "bob", "2.0", 1)]);
+    drop(bc);
+
+    let path = dir.join(BLOCKS_FILE);
+    let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(b"deadbeef\t{\"index\":3,\"trunc").unwrap();
+    drop(file);
+
+    let store = BlockStore::open(&dir).unwrap();
+    assert_eq!(store.load().unwrap().len(), 3);
+    // File udah di-truncate balik ke record valid terakhir
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(contents.lines().count(), 3);
+}
+
+#[test]
+fn test_tail_cut_inside_a_utf8_character_is_discarded() {
+    let dir = temp_data_dir("corrupt-utf8");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    bc.add_block(vec![transfer("genesis", "bob", "1.0", 0)]);
+    drop(bc);
+
+    // "€" = E2 82 AC, write-nya kepotong setelah dua byte
+    let path = dir.join(BLOCKS_FILE);
+    let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(b"deadbeef\t{\"index\":2,\"memo\":\"\xE2\x82").unwrap();
+    drop(file);
+
+    assert_eq!(BlockStore::open(&dir).unwrap().load().unwrap().len(), 2);
+    assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+}
+
+#[test]
+fn test_tampered_block_is_rejected() {
+    let dir = temp_data_dir("tamper");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    bc.add_block(vec![transfer("genesis", "bob", "1.0", 0)]);
+    bc.add_block(vec![transfer("genesis", "bob", "2.0", 1)]);
+    drop(bc);
+
+    let path = dir.join(BLOCKS_FILE);
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let tampered = contents.replacen("\"amount\":\"2000000000\"", "\"amount\":\"200000000000\"", 1);
+    assert_ne!(contents, tampered);
+    std::fs::write(&path, tampered).unwrap();
+
+    let store = BlockStore::open(&dir).unwrap();
+    assert_eq!(store.load().unwrap().len(), 2);
+}
+
+#[test]
+fn test_corruption_in_the_middle_is_an_error() {
+    let dir = temp_data_dir("corrupt-middle");
+    let mut bc = Blockchain::open(&dir).unwrap();
+    bc.add_block(vec![transfer("genesis", "bob", "1.0", 0)]);
+    bc.add_block(vec![transfer("genesis", "bob", "2.0", 1)]);
+    drop(bc);
+
+    // Block #1 diubah, block #2 sesudahnya masih utuh
+    let path = dir.join(BLOCKS_FILE);
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let tampered = contents.replacen("\"amount\":\"1000000000\"", "\"amount\":\"100000000000\"", 1);
+    assert_ne!(contents, tampered);
+    std::fs::write(&path, &tampered).unwrap();
+
+    let store = BlockStore::open(&dir).unwrap();
+    assert_eq!(store.load().unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    assert!(Blockchain::open(&dir).is_err());
+    // Ga ada yang dipotong
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), tampered);
+}
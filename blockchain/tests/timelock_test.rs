@@ -0,0 +1,120 @@
+use blockchain::chainspec::{self, ChainSpec};
+use blockchain::mempool::Mempool;
+use blockchain::validation::{self, ValidationErrorKind};
+use blockchain::miner::MinerState;
+use blockchain::{mining, Blockchain, LockTime, Transaction};
+
+mod common;
+use common::sol;
+
+fn now() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+fn payout(amount: &str, nonce: u64, lock: Option<LockTime>) -> Transaction {
+    let mut tx = Transaction::new(chainspec::dev_genesis_address(), "alice".to_string(), sol(amount), "15000 IDR".parse().unwrap(), "SOL".to_string(), sol("0.11"))
+        .with_nonce(nonce);
+    if let Some(lock) = lock {
+        tx = tx.with_lock(lock);
+    }
+    tx.sign(&chainspec::dev_genesis_keypair());
+    tx
+}
+
+#[test]
+fn test_height_lock_is_held_until_mature() {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    let locked = payout("1", 0, Some(LockTime::Height(3)));
+    bc.submit_transaction(locked.clone()).unwrap();
+    // Transaksi berikutnya dari sender yang sama ikut nunggu
+    bc.submit_transaction(payout("2", 1, None)).unwrap();
+
+    assert_eq!(bc.produce_block(), None);
+    assert_eq!(bc.mempool.len(), 2);
+    // Dipaksa lewat add_block juga tetap dibuang producer
+    bc.add_block(vec![locked.clone()]);
+    assert!(bc.chain[1].transactions.is_empty());
+    bc.add_block(vec![]);
+
+    assert_eq!(bc.produce_block(), Some(3));
+    assert!(bc.chain[3].transactions.iter().any(|tx| tx.txid == locked.txid));
+    assert!(bc.mempool.is_empty());
+    assert_eq!(bc.wallet.get_balance("alice", "SOL"), sol("3"));
+    assert_eq!(bc.validate_chain(), Ok(()));
+}
+
+#[test]
+fn test_validator_rejects_immature_transactions() {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    let unlock = now() + 3600;
+    let locked = payout("1", 0, Some(LockTime::Time(unlock)));
+    let tip = bc.chain[0].clone();
+    let block = mining::mine_block(1, tip.hash.clone(), tip.timestamp, vec![locked.clone()], bc.difficulty, &bc.spec, &bc.wallet);
+
+    let err = bc.accept_block(block.clone()).unwrap_err();
+    assert!(err.contains("is locked until time"), "{}", err);
+    assert_eq!(bc.chain.len(), 1);
+
+    let mut chain = bc.chain.clone();
+    chain.push(block);
+    let err = validation::validate_blocks(&chain, &bc.spec).unwrap_err();
+    assert_eq!(err.block_index, 1);
+    assert_eq!(err.kind, ValidationErrorKind::ImmatureTransaction { txid: locked.txid.clone(), lock_until: LockTime::Time(unlock) });
+
+    // Lock yang udah dilewati median time past (di sini timestamp genesis) ga ngaruh
+    let matured = payout("1", 0, Some(LockTime::Time(bc.chain[0].timestamp)));
+    bc.submit_transaction(matured).unwrap();
+    assert_eq!(bc.produce_block(), Some(1));
+}
+
+#[test]
+fn test_time_lock_follows_median_time_past() {
+    let mut bc = Blockchain::in_memory(ChainSpec::default());
+    let unlock = now();
+    let locked = payout("1", 0, Some(LockTime::Time(unlock)));
+    let genesis = bc.chain[0].clone();
+
+    // Timestamp block-nya udah lewat lock, tapi median time past masih di genesis
+    let block = mining::mine_block(1, genesis.hash.clone(), genesis.timestamp, vec![locked.clone()], bc.difficulty, &bc.spec, &bc.wallet);
+    assert!(block.timestamp >= unlock);
+    let err = bc.accept_block(block).unwrap_err();
+    assert!(err.contains("is locked until time"), "{}", err);
+
+    // Majuin timestamp jauh ke depan juga ga bisa
+    let mut future = mining::build_candidate(1, genesis.hash.clone(), genesis.timestamp, vec![], bc.difficulty, &bc.spec, &bc.wallet);
+    future.timestamp = unlock + 3600;
+    let future = mining::mine_candidate(future, 1, &MinerState::default()).unwrap();
+    let err = bc.accept_block(future).unwrap_err();
+    assert!(err.contains("too far in the future"), "{}", err);
+    assert_eq!(bc.chain.len(), 1);
+
+    bc.add_block(vec![]);
+    bc.add_block(vec![]);
+    assert!(validation::median_time_past(&bc.chain) >= unlock);
+    bc.submit_transaction(locked.clone()).unwrap();
+    assert_eq!(bc.produce_block(), Some(3));
+    assert_eq!(bc.validate_chain(), Ok(()));
+}
+
+#[test]
+fn test_lock_is_signed_and_holds_mempool_expiry() {
+    let mut tampered = payout("1", 0, Some(LockTime::Height(100)));
+    tampered.lock_until = None;
+    assert!(tampered.validate().is_err());
+
+    let json = serde_json::to_value(payout("1", 0, Some(LockTime::Height(7)))).unwrap();
+    assert_eq!(json["lock_until"], serde_json::json!({"height": 7}));
+
+    let spec = ChainSpec::default();
+    let mut wallet = blockchain::Wallet::new();
+    wallet.credit(&chainspec::dev_genesis_address(), "SOL", sol("10")).unwrap();
+    let mut mempool = Mempool::new(60, 100);
+    mempool.insert(payout("1", 0, Some(LockTime::Height(5))), &wallet, &spec, 100).unwrap();
+
+    // Masih dikunci: ga kadaluarsa, umurnya baru dihitung setelah mature
+    assert!(mempool.expire(1_000, 4).is_empty());
+    assert!(mempool.select(&wallet, &spec, 4, 1_000, 10).is_empty());
+    assert_eq!(mempool.select(&wallet, &spec, 5, 1_000, 10).len(), 1);
+    assert!(mempool.expire(1_050, 5).is_empty());
+    assert_eq!(mempool.expire(1_100, 5).len(), 1);
+}
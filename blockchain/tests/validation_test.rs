@@ -0,0 +1,61 @@
+use blockchain::validation::ValidationErrorKind;
+use blockchain::Blockchain;
+use std::path::Path;
+
+mod common;
+use common::{temp_data_dir, sol, transfer};
+
+fn sample_chain(dir: &Path) -> Blockchain {
+    let mut bc = Blockchain::open(dir).unwrap();
+    bc.add_block(vec![transfer("genesis", "alice", "10.0", 0)]);
+    bc.add_block(vec![transfer("alice", "bob", "4.0", 0)]);
+    bc.add_block(vec![transfer("bob", "carol", "1.0", 0)]);
+    bc
+}
+
+#[test]
+fn test_valid_chain_passes() {
+    let dir = temp_data_dir("valid");
+    let bc = sample_chain(&dir);
+    assert_eq!(bc.validate_chain(), Ok(()));
+}
+
+#[test]
+fn test_tampered_transaction_is_reported() {
+    let dir = temp_data_dir("tampered");
+    let mut bc = sample_chain(&dir);
+    bc.chain[2].transactions[0].amount = sol("3");
+    let err = bc.validate_chain().unwrap_err();
+    assert_eq!(err.block_index, 2);
+    assert!(matches!(err.kind, ValidationErrorKind::MerkleMismatch { .. }));
+}
+
+#[test]
+fn test_broken_link_is_reported() {
+    let dir = temp_data_dir("link");
+    let mut bc = sample_chain(&dir);
+    bc.chain[3].previous_hash = "00ff".to_string();
+    let err = bc.validate_chain().unwrap_err();
+    assert_eq!(err.block_index, 3);
+    assert!(matches!(err.kind, ValidationErrorKind::BrokenLink { .. }));
+}
+
+#[test]
+fn test_missing_block_is_reported() {
+    let dir = temp_data_dir("gap");
+    let mut bc = sample_chain(&dir);
+    bc.chain.remove(2);
+    let err = bc.validate_chain().unwrap_err();
+    assert_eq!(err.block_index, 3);
+    assert_eq!(err.kind, ValidationErrorKind::IndexMismatch { expected: 2, found: 3 });
+}
+
+#[test]
+fn test_tampered_header_is_reported() {
+    let dir = temp_data_dir("header");
+    let mut bc = sample_chain(&dir);
+    bc.chain[2].nonce += 1;
+    let err = bc.validate_chain().unwrap_err();
+    assert_eq!(err.block_index, 2);
+    assert!(matches!(err.kind, ValidationErrorKind::HashMismatch { .. }));
+}
@@ -1,22 +1,25 @@
 use serde::{Serialize, Deserialize};
 use chrono::{Utc, FixedOffset};
 use uuid::Uuid;
+use wallet::amount::Amount;
+use wallet::asset::AssetRegistry;
+use wallet::peg::Peg;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Transaction {
     pub sender: String,
     pub receiver: String,
-    pub amount: f64,
-    pub peg_value: String,
+    pub amount: Amount,
+    pub peg_value: Peg,
     pub network: String,
-    pub fee: f64,
+    pub fee: Amount,
     pub status: String,
     pub txid: String,
     pub date: String,
 }
 
 impl Transaction {
-    pub fn new(sender: String, receiver: String, amount: f64, peg_value: String, network: String, fee: f64) -> Self {
+    pub fn new(sender: String, receiver: String, amount: Amount, peg_value: Peg, network: String, fee: Amount) -> Self {
         let txid = Uuid::new_v4().to_string();
         let date = Utc::now()
             .with_timezone(&FixedOffset::east_opt(7 * 3600).unwrap())
@@ -35,25 +38,17 @@ impl Transaction {
         }
     }
 
-    pub fn validate(&self) -> Result<(), String> {
-        if self.amount <= 0.0 {
+    // Network, peg, dan fee minimum dicek ke registry aset dari chainspec
+    pub fn validate(&self, assets: &AssetRegistry) -> Result<(), String> {
+        if self.amount.is_zero() {
             return Err("Amount must be greater than zero".to_string());
         }
-        if self.fee <= 0.0 {
-            return Err("Fee must be greater than zero".to_string());
+        let network = self.network.trim();
+        assets.check_peg(network, &self.peg_value)?;
+        let base_fee = assets.require(network)?.fees.base_fee;
+        if self.fee.is_zero() || self.fee < base_fee {
+            return Err(format!("Fee {} is below the minimum {} for {}", self.fee, base_fee, network));
         }
-
-        let supported_networks = ["BTC", "ETH", "SOL", "BNB", "ADA", "XRP", "DOT", "MATIC", "USDT"];
-        let network_trim = self.network.trim();
-        if !supported_networks.contains(&network_trim) {
-            return Err(format!("Unsupported network: {}. Supported: {:?}", network_trim, supported_networks));
-        }
-
-        let peg_value_trim = self.peg_value.trim().to_uppercase();
-        if !peg_value_trim.ends_with("IDR") && !peg_value_trim.ends_with("USD") && !peg_value_trim.ends_with("USDT") {
-            return Err(format!("Unsupported peg value unit: {}. Use IDR, USD, or USDT.", peg_value_trim));
-        }
-
         Ok(())
     }
 }
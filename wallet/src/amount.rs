@@ -0,0 +1,142 @@
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+// Jumlah token dalam satuan terkecil (lamports, satoshi, wei). Ga ada f64 sama sekali,
+// jadi 0.1 SOL = 100_000_000 lamports persis. Di JSON ditulis sebagai string angka
+// satuan terkecil biar ga kepotong presisi double di client.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Amount(u128);
+
+impl Amount {
+    pub const ZERO: Amount = Amount(0);
+
+    pub const fn from_units(units: u128) -> Self {
+        Amount(units)
+    }
+
+    pub const fn units(self) -> u128 {
+        self.0
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn checked_add(self, other: Amount) -> Result<Amount, String> {
+        self.0.checked_add(other.0).map(Amount).ok_or_else(|| format!("Amount overflow: {} + {}", self, other))
+    }
+
+    pub fn checked_sub(self, other: Amount) -> Result<Amount, String> {
+        self.0.checked_sub(other.0).map(Amount).ok_or_else(|| format!("Amount underflow: {} - {}", self, other))
+    }
+
+    pub fn checked_mul(self, factor: u128) -> Result<Amount, String> {
+        self.0.checked_mul(factor).map(Amount).ok_or_else(|| format!("Amount overflow: {} * {}", self, factor))
+    }
+
+    // Pembagian integer, sisanya dibuang
+    pub fn div_floor(self, divisor: u128) -> Amount {
+        Amount(self.0.checked_div(divisor).unwrap_or(0))
+    }
+
+    pub fn saturating_add(self, other: Amount) -> Amount {
+        Amount(self.0.saturating_add(other.0))
+    }
+
+    pub fn saturating_sub(self, other: Amount) -> Amount {
+        Amount(self.0.saturating_sub(other.0))
+    }
+
+    // "1.5" dengan 9 desimal -> 1_500_000_000. Digit lebih dari `decimals` ditolak, bukan dibulatin.
+    pub fn parse_decimal(value: &str, decimals: u8) -> Result<Amount, String> {
+        let value = value.trim();
+        let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+        let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+            return Err(format!("Invalid amount: {:?}", value));
+        }
+        if fraction.len() > decimals as usize {
+            return Err(format!("Amount {} has more than {} decimal places", value, decimals));
+        }
+        let overflow = || format!("Amount {} is too large", value);
+        let scale = 10u128.checked_pow(decimals as u32).ok_or_else(overflow)?;
+        let whole: u128 = if whole.is_empty() { 0 } else { whole.parse().map_err(|_| overflow())? };
+        let padded = format!("{:0<width$}", fraction, width = decimals as usize);
+        let fraction: u128 = if padded.is_empty() { 0 } else { padded.parse().map_err(|_| overflow())? };
+        whole
+            .checked_mul(scale)
+            .and_then(|w| w.checked_add(fraction))
+            .map(Amount)
+            .ok_or_else(overflow)
+    }
+
+    // Kebalikan `parse_decimal`, nol di belakang koma dibuang
+    pub fn to_decimal_string(self, decimals: u8) -> String {
+        let scale = match 10u128.checked_pow(decimals as u32) {
+            Some(scale) => scale,
+            None => return self.0.to_string(),
+        };
+        let whole = self.0 / scale;
+        let fraction = self.0 % scale;
+        if fraction == 0 {
+            return whole.to_string();
+        }
+        let fraction = format!("{:0>width$}", fraction, width = decimals as usize);
+        format!("{}.{}", whole, fraction.trim_end_matches('0'))
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Amount {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("Invalid amount: {:?}, expected an integer number of base units", s));
+        }
+        s.parse().map(Amount).map_err(|_| format!("Amount {} is too large", s))
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+// Terima string ("1500") atau angka bulat JSON (1500); angka pecahan ditolak
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AmountVisitor;
+
+        impl Visitor<'_> for AmountVisitor {
+            type Value = Amount;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an integer number of base units as a string")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Amount, E> {
+                value.parse().map_err(E::custom)
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Amount, E> {
+                Ok(Amount(value as u128))
+            }
+
+            fn visit_u128<E: de::Error>(self, value: u128) -> Result<Amount, E> {
+                Ok(Amount(value))
+            }
+        }
+
+        deserializer.deserialize_any(AmountVisitor)
+    }
+}
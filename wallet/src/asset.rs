@@ -0,0 +1,149 @@
+use crate::amount::Amount;
+use crate::peg::{Peg, PEG_DECIMALS};
+use serde::{Deserialize, Serialize};
+
+// Dibatasi presisi peg, biar jumlah aset selalu bisa ditulis sebagai peg ke simbolnya sendiri
+pub const MAX_DECIMALS: u8 = PEG_DECIMALS;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeeSchedule {
+    // Fee minimum per transaksi (satuan terkecil)
+    pub base_fee: Amount,
+    // Tambahan per byte transaksi
+    #[serde(default)]
+    pub per_byte_fee: Amount,
+}
+
+impl FeeSchedule {
+    pub fn minimum_fee(&self, size: usize) -> Result<Amount, String> {
+        self.base_fee.checked_add(self.per_byte_fee.checked_mul(size as u128)?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Asset {
+    pub symbol: String,
+    pub decimals: u8,
+    pub fees: FeeSchedule,
+    // Batas total saldo yang boleh ada (satuan terkecil), kosong = ga dibatasi
+    #[serde(default)]
+    pub supply_cap: Option<Amount>,
+    // Mata uang yang boleh dipakai di `peg_value`. Simbol aset sendiri selalu boleh.
+    pub pegs: Vec<String>,
+}
+
+fn valid_code(code: &str) -> bool {
+    (1..=10).contains(&code.len()) && code.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+}
+
+impl Asset {
+    pub fn validate(&self) -> Result<(), String> {
+        if !valid_code(&self.symbol) {
+            return Err(format!("Invalid asset symbol {:?}: use 1-10 uppercase letters or digits", self.symbol));
+        }
+        if self.decimals > MAX_DECIMALS {
+            return Err(format!("Asset {} has {} decimals, maximum is {}", self.symbol, self.decimals, MAX_DECIMALS));
+        }
+        if let Some(peg) = self.pegs.iter().find(|p| !valid_code(p)) {
+            return Err(format!("Invalid peg currency {:?} for asset {}", peg, self.symbol));
+        }
+        Ok(())
+    }
+
+    pub fn parse_amount(&self, value: &str) -> Result<Amount, String> {
+        Amount::parse_decimal(value, self.decimals)
+    }
+
+    pub fn display_amount(&self, amount: Amount) -> String {
+        amount.to_decimal_string(self.decimals)
+    }
+
+    pub fn native_peg(&self, amount: Amount) -> Peg {
+        Peg::native(amount, self.decimals, &self.symbol)
+    }
+
+    pub fn allows_peg(&self, currency: &str) -> bool {
+        currency == self.symbol || self.pegs.iter().any(|p| p == currency)
+    }
+}
+
+// Daftar aset yang dikenal chain, diurutkan per simbol
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AssetRegistry {
+    assets: Vec<Asset>,
+}
+
+impl AssetRegistry {
+    pub fn new(assets: Vec<Asset>) -> Result<Self, String> {
+        let mut registry = AssetRegistry::default();
+        for asset in assets {
+            if registry.get(&asset.symbol).is_some() {
+                return Err(format!("Asset {} is registered twice", asset.symbol));
+            }
+            registry.upsert(asset)?;
+        }
+        Ok(registry)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        Self::new(self.assets.clone()).map(|_| ())
+    }
+
+    pub fn assets(&self) -> &[Asset] {
+        &self.assets
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<&Asset> {
+        self.assets.iter().find(|a| a.symbol == symbol)
+    }
+
+    pub fn require(&self, symbol: &str) -> Result<&Asset, String> {
+        self.get(symbol).ok_or_else(|| {
+            let supported: Vec<&str> = self.assets.iter().map(|a| a.symbol.as_str()).collect();
+            format!("Unsupported network: {}. Supported: {:?}", symbol, supported)
+        })
+    }
+
+    // Tambah atau ganti aset, balikin versi lamanya kalau ada
+    pub fn upsert(&mut self, asset: Asset) -> Result<Option<Asset>, String> {
+        asset.validate()?;
+        match self.assets.binary_search_by(|a| a.symbol.cmp(&asset.symbol)) {
+            Ok(i) => Ok(Some(std::mem::replace(&mut self.assets[i], asset))),
+            Err(i) => {
+                self.assets.insert(i, asset);
+                Ok(None)
+            }
+        }
+    }
+
+    pub fn remove(&mut self, symbol: &str) -> Option<Asset> {
+        let i = self.assets.iter().position(|a| a.symbol == symbol)?;
+        Some(self.assets.remove(i))
+    }
+
+    // Network harus terdaftar dan mata uang peg-nya diizinkan aset itu
+    pub fn check_peg(&self, network: &str, peg: &Peg) -> Result<(), String> {
+        let asset = self.require(network)?;
+        if !asset.allows_peg(&peg.currency) {
+            return Err(format!("Unsupported peg currency {} for {}. Use one of {:?}", peg.currency, network, asset.pegs));
+        }
+        Ok(())
+    }
+
+    // Peg ke simbol network sendiri; aset yang ga terdaftar dianggap 0 desimal
+    pub fn native_peg(&self, amount: Amount, network: &str) -> Peg {
+        match self.get(network) {
+            Some(asset) => asset.native_peg(amount),
+            None => Peg::native(amount, 0, network),
+        }
+    }
+
+    // Buat tampilan doang; aset yang ga terdaftar ditampilin dalam satuan terkecil
+    pub fn display_amount(&self, amount: Amount, network: &str) -> String {
+        match self.get(network) {
+            Some(asset) => asset.display_amount(amount),
+            None => amount.to_string(),
+        }
+    }
+}
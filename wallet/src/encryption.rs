@@ -1,4 +1,5 @@
-use ed25519_dalek::{Keypair, PublicKey};
+pub use ed25519_dalek::{Keypair, PublicKey};
+use ed25519_dalek::{SecretKey, Signature, Signer};
 use rand::rngs::OsRng;
 use bs58;
 
@@ -6,6 +7,36 @@ pub fn generate_keypair() -> Keypair {
     Keypair::generate(&mut OsRng)
 }
 
+// Keypair deterministik dari seed 32 byte, buat akun dev/test yang kuncinya udah diketahui
+pub fn keypair_from_seed(seed: [u8; 32]) -> Keypair {
+    let secret = SecretKey::from_bytes(&seed).expect("seed is always 32 bytes");
+    let public = PublicKey::from(&secret);
+    Keypair { secret, public }
+}
+
 pub fn get_address(public_key: &PublicKey) -> String {
     bs58::encode(public_key.to_bytes()).into_string()
-}
\ No newline at end of file
+}
+
+pub fn public_key_from_address(address: &str) -> Result<PublicKey, String> {
+    let bytes = bs58::decode(address)
+        .into_vec()
+        .map_err(|e| format!("Address {} is not valid base58: {}", address, e))?;
+    PublicKey::from_bytes(&bytes).map_err(|_| format!("Address {} is not an ed25519 public key", address))
+}
+
+// Signature di-encode base58, sama kayak address
+pub fn sign(keypair: &Keypair, message: &[u8]) -> String {
+    bs58::encode(keypair.sign(message).to_bytes()).into_string()
+}
+
+pub fn verify(address: &str, message: &[u8], signature: &str) -> Result<(), String> {
+    let public_key = public_key_from_address(address)?;
+    let bytes = bs58::decode(signature)
+        .into_vec()
+        .map_err(|e| format!("Signature is not valid base58: {}", e))?;
+    let signature = Signature::from_bytes(&bytes).map_err(|_| "Signature must be 64 bytes".to_string())?;
+    public_key
+        .verify_strict(message, &signature)
+        .map_err(|_| format!("Signature does not match sender {}", address))
+}
@@ -1,20 +1,28 @@
 use serde::{Serialize, Deserialize};
 
+pub mod amount;
+pub mod asset;
+pub mod encryption;
+pub mod multisig;
+pub mod peg;
+
+use amount::Amount;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Wallet {
-    pub balance: u64,
+    pub balance: Amount,
     pub address: String,
 }
 
 impl Wallet {
     pub fn new() -> Self {
         Wallet {
-            balance: 100,
+            balance: Amount::from_units(100),
             address: "default_wallet_address".to_string(),
         }
     }
 
-    pub fn can_transfer(&self, amount: u64) -> Result<(), String> {
+    pub fn can_transfer(&self, amount: Amount) -> Result<(), String> {
         if self.balance >= amount {
             Ok(())
         } else {
@@ -0,0 +1,135 @@
+use crate::encryption::{self, Keypair};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+pub const MAX_SIGNERS: usize = 16;
+// Byte pertama address multisig. Address biasa = 32 byte public key, address multisig = 33 byte,
+// jadi dua jenis address ga mungkin ketuker.
+const ADDRESS_VERSION: u8 = 0x05;
+
+// Aturan m-of-n: minimal `threshold` dari `signers` (address ed25519 biasa) harus tanda tangan.
+// Signer selalu diurutkan, jadi urutan waktu bikin ga ngaruh ke address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultisigPolicy {
+    pub threshold: u8,
+    pub signers: Vec<String>,
+}
+
+impl MultisigPolicy {
+    pub fn new(threshold: u8, mut signers: Vec<String>) -> Result<Self, String> {
+        signers.sort();
+        let policy = MultisigPolicy { threshold, signers };
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let n = self.signers.len();
+        if n == 0 || n > MAX_SIGNERS {
+            return Err(format!("Multisig needs 1 to {} signers, got {}", MAX_SIGNERS, n));
+        }
+        if self.threshold == 0 || self.threshold as usize > n {
+            return Err(format!("Multisig threshold must be between 1 and {}, got {}", n, self.threshold));
+        }
+        if self.signers.windows(2).any(|w| w[0] >= w[1]) {
+            return Err("Multisig signers must be sorted and unique".to_string());
+        }
+        for signer in &self.signers {
+            encryption::public_key_from_address(signer)?;
+        }
+        Ok(())
+    }
+
+    // base58(versi || sha256(threshold || public key tiap signer))
+    pub fn address(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update([self.threshold]);
+        for signer in &self.signers {
+            hasher.update(bs58::decode(signer).into_vec().unwrap_or_default());
+        }
+        let mut bytes = vec![ADDRESS_VERSION];
+        bytes.extend_from_slice(&hasher.finalize());
+        bs58::encode(bytes).into_string()
+    }
+}
+
+pub fn is_multisig_address(address: &str) -> bool {
+    matches!(bs58::decode(address).into_vec(), Ok(bytes) if bytes.len() == 33 && bytes[0] == ADDRESS_VERSION)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartialSignature {
+    pub signer: String,
+    pub signature: String,
+}
+
+// Yang dibawa transaksi dari address multisig: policy-nya (harus cocok sama address)
+// plus tanda tangan yang udah terkumpul
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultisigWitness {
+    pub policy: MultisigPolicy,
+    #[serde(default)]
+    pub signatures: Vec<PartialSignature>,
+}
+
+impl MultisigWitness {
+    pub fn new(policy: MultisigPolicy) -> Self {
+        MultisigWitness { policy, signatures: vec![] }
+    }
+
+    // Tambah (atau ganti) tanda tangan satu signer, dicek dulu ke `message`
+    pub fn add_signature(&mut self, message: &[u8], signature: PartialSignature) -> Result<(), String> {
+        if !self.policy.signers.contains(&signature.signer) {
+            return Err(format!("{} is not a signer of this multisig", signature.signer));
+        }
+        encryption::verify(&signature.signer, message, &signature.signature)?;
+        self.signatures.retain(|s| s.signer != signature.signer);
+        self.signatures.push(signature);
+        self.signatures.sort_by(|a, b| a.signer.cmp(&b.signer));
+        Ok(())
+    }
+
+    pub fn sign(&mut self, keypair: &Keypair, message: &[u8]) -> Result<(), String> {
+        let signer = encryption::get_address(&keypair.public);
+        self.add_signature(message, PartialSignature { signer, signature: encryption::sign(keypair, message) })
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.signatures.len() >= self.policy.threshold as usize
+    }
+
+    // Signer yang belum tanda tangan
+    pub fn missing_signers(&self) -> Vec<String> {
+        self.policy.signers.iter().filter(|s| !self.signatures.iter().any(|sig| &sig.signer == *s)).cloned().collect()
+    }
+
+    // Policy harus punya `address`, semua tanda tangan valid dari signer berbeda, dan jumlahnya
+    // minimal threshold
+    pub fn verify(&self, address: &str, message: &[u8]) -> Result<(), String> {
+        self.policy.validate()?;
+        if self.policy.address() != address {
+            return Err(format!("Multisig policy does not match address {}", address));
+        }
+        let mut seen: Vec<&str> = vec![];
+        for sig in &self.signatures {
+            if !self.policy.signers.contains(&sig.signer) {
+                return Err(format!("{} is not a signer of multisig {}", sig.signer, address));
+            }
+            if seen.contains(&sig.signer.as_str()) {
+                return Err(format!("Duplicate signature from {}", sig.signer));
+            }
+            encryption::verify(&sig.signer, message, &sig.signature)?;
+            seen.push(&sig.signer);
+        }
+        if !self.is_complete() {
+            return Err(format!(
+                "Multisig {} needs {} of {} signatures, got {}",
+                address,
+                self.policy.threshold,
+                self.policy.signers.len(),
+                self.signatures.len()
+            ));
+        }
+        Ok(())
+    }
+}
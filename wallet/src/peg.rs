@@ -0,0 +1,68 @@
+use crate::amount::Amount;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+// Presisi jumlah peg, cukup buat peg ke simbol aset sendiri (aset paling banyak 18 desimal)
+pub const PEG_DECIMALS: u8 = 18;
+
+// Nilai yang dideklarasiin sender buat transaksi, misal "15000 IDR" atau "1.5 USD".
+// Di JSON dan di payload yang ditandatangani selalu bentuk kanonik `to_string()`:
+// angka tanpa nol di belakang koma, spasi, kode mata uang huruf besar.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Peg {
+    pub amount: Amount,
+    pub currency: String,
+}
+
+impl Peg {
+    pub fn new(amount: Amount, currency: &str) -> Self {
+        Peg { amount, currency: currency.to_uppercase() }
+    }
+
+    // Peg ke simbol aset sendiri, misal reward miner "50 SOL"
+    pub fn native(amount: Amount, decimals: u8, symbol: &str) -> Self {
+        let scale = 10u128.pow(PEG_DECIMALS.saturating_sub(decimals) as u32);
+        let units = if decimals > PEG_DECIMALS {
+            amount.units() / 10u128.pow((decimals - PEG_DECIMALS) as u32)
+        } else {
+            amount.units().saturating_mul(scale)
+        };
+        Peg::new(Amount::from_units(units), symbol)
+    }
+}
+
+impl fmt::Display for Peg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount.to_decimal_string(PEG_DECIMALS), self.currency)
+    }
+}
+
+// "15000 IDR", "15000IDR", "1.5 usd"
+impl FromStr for Peg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        let amount = value.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+        let currency = &value[amount.len()..];
+        if currency.is_empty() {
+            return Err(format!("Peg value {:?} has no currency", s));
+        }
+        let amount = Amount::parse_decimal(amount.trim(), PEG_DECIMALS).map_err(|e| format!("Invalid peg value {:?}: {}", s, e))?;
+        Ok(Peg::new(amount, currency))
+    }
+}
+
+impl Serialize for Peg {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Peg {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+    }
+}